@@ -6,6 +6,20 @@ use thiserror::Error;
 pub enum EchoError {
     #[error("Instruction not implemented.")]
     NotImplemented,
+    #[error("Account must be writable.")]
+    AccountMustBeWritable,
+    #[error("Invalid account owner.")]
+    InvalidAccountOwner,
+    #[error("Invalid account address.")]
+    InvalidAccountAddress,
+    #[error("Invalid program address.")]
+    InvalidProgramAddress,
+    #[error("The same account was passed more than once.")]
+    DuplicateAccount,
+    #[error("Invalid account data.")]
+    InvalidAccountData,
+    #[error("Account uses an old layout, run Migrate first.")]
+    AccountNeedsMigration,
 }
 
 impl From<EchoError> for ProgramError {
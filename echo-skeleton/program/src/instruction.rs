@@ -1,4 +1,11 @@
 use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    system_program,
+};
+
+use crate::state::{AuthorizedBufferHeader, VendingMachineBufferHeader};
 
 #[derive(BorshSerialize, BorshDeserialize, Debug, Clone)]
 pub enum EchoInstruction {
@@ -16,28 +23,30 @@ pub enum EchoInstruction {
     Echo { data : Vec<u8> },
     /// This instruction will allocate `buffer_size` bytes to the `authorized_buffer` account and assign it the Echo Program.
     ///
-    /// The first 9 bytes of authorized_buffer will be set with the following data:
-    ///     byte 0: bump_seed
-    ///     bytes 1-8: buffer_seed
+    /// The first 11 bytes of authorized_buffer will be set with the following data:
+    ///     byte 0: discriminator (1)
+    ///     byte 1: version
+    ///     byte 2: bump_seed
+    ///     bytes 3-10: buffer_seed
     ///
     /// Accounts:
     /// | index | writable | signer | description                                                              |
     /// |-------|----------|--------|--------------------------------------------------------------------------|
     /// | 0     | ✅       | ❌     | authorized_buffer: PDA of Echo Program that only `authority` can write to |
-    /// | 1     | ❌       | ✅     | authority: Pubkey with sole write access to `authorized_buffer`           |
+    /// | 1     | ✅       | ✅     | authority: Pubkey with sole write access to `authorized_buffer`           |
     /// | 2     | ❌       | ❌     | system_program: Used to allocate the buffer                               |
     InitializeAuthorizedEcho {
         buffer_seed: u64,
         buffer_size: usize,
     },
     /// The contents of the data vector that is provided to the instruction will be copied into the `authorized_buffer` account
-    /// starting from index 9 (will NOT override the bump_seed and buffer_seed).
+    /// starting from index 11 (will NOT override the header).
     ///
     /// If the remaining `authorized_buffer` account length ( N ) is smaller than the length of `data`, copy the first N bytes
     /// of data into `authorized_buffer`.
     ///
-    /// Initially, if `authorized_buffer` has any non-zero data past index 9, you should should zero out all of the data outside
-    /// of the first 9 bytes.
+    /// Initially, if `authorized_buffer` has any non-zero data past index 11, you should should zero out all of the data outside
+    /// of the first 11 bytes.
     ///
     /// If any account besides the `authority` attempts to write to the `authorized_buffer`, the instruction will fail.
     ///
@@ -49,16 +58,18 @@ pub enum EchoInstruction {
     AuthorizedEcho { data: Vec<u8> },
     /// This instruction will allocate `buffer_size` bytes to the `vending_machine_buffer` account and assign it the Echo Program.
     ///
-    /// The first 9 bytes of `vending_machine_buffer` will be set with the following data:
-    ///     byte 0: bump_seed
-    ///     bytes 1-8: price
+    /// The first 11 bytes of `vending_machine_buffer` will be set with the following data:
+    ///     byte 0: discriminator (2)
+    ///     byte 1: version
+    ///     byte 2: bump_seed
+    ///     bytes 3-10: price
     ///
     /// Accounts:
     /// | index | writable | signer | description                                                                                         |
     /// |-------|----------|--------|-----------------------------------------------------------------------------------------------------|
     /// | 0     | ✅       | ❌     | vending_machine_buffer: PDA of the Echo Program that only holders of a particular token can write to |
     /// | 1     | ❌       | ❌     | vending_machine_mint: Pubkey with sole write access to `authorized_buffer`                           |
-    /// | 2     | ❌       | ✅     | payer: Pubkey that allocates the `vending_machine_buffer`                                            |
+    /// | 2     | ✅       | ✅     | payer: Pubkey that allocates the `vending_machine_buffer`                                            |
     /// | 3     | ❌       | ❌     | system_program: Used to allocate the buffer                                                          |
     InitializeVendingMachineEcho {
         // Number of tokens required change the buffer
@@ -66,13 +77,13 @@ pub enum EchoInstruction {
         buffer_size: usize,
     },
     /// The contents of the data vector that is provided to the instruction should be copied into the account starting from
-    /// index 9 (you do NOT want to override the bump_seed and price).
+    /// index 11 (you do NOT want to override the header).
     ///
     /// If the remaining account length ( N ) is smaller than the length of data, copy the first N bytes of data into
     /// `vending_machine_buffer`.
     ///
-    /// Initially, if `vending_machine_buffer` has any non-zero data past index 9, you should should zero out all of the
-    /// data outside of the first 9 bytes.
+    /// Initially, if `vending_machine_buffer` has any non-zero data past index 11, you should should zero out all of the
+    /// data outside of the first 11 bytes.
     ///
     /// Before any data is copied over, the user must burn a `price` amount of tokens from the `user_token_account`.
     /// This will require a cross program invocation to the Token Program. If this instruction succeed (verifies that the
@@ -88,7 +99,158 @@ pub enum EchoInstruction {
     /// | 0     | ✅       | ❌     | vending_machine_buffer: PDA of the Echo Program that only holders of a particular token can write to |
     /// | 1     | ❌       | ✅     | user: This is authority of the token account that is using the vending machine                       |
     /// | 2     | ✅       | ❌     | user_token_account: This is the token account that will pay for the use of the vending machine       |
-    /// | 3     | ❌       | ❌     | vending_machine_mint: This is the token mint that is accepted by the `vending_machine_buffer`        |
-    /// | 3     | ❌       | ❌     | token_program: Used to burn the vending machine tokens                                               |
+    /// | 3     | ✅       | ❌     | vending_machine_mint: This is the token mint that is accepted by the `vending_machine_buffer`        |
+    /// | 4     | ❌       | ❌     | token_program: Used to burn the vending machine tokens                                               |
     VendingMachineEcho { data: Vec<u8> },
+    /// Upgrades a buffer written with the version 0 layout to the current header. Version 0 headers were only the
+    /// bump_seed followed by the buffer_seed of an `authorized_buffer` or the price of a `vending_machine_buffer`. The
+    /// buffer grows by 2 bytes and its echoed data is kept. Buffers already on the current layout are left alone, and
+    /// anything else is refused.
+    ///
+    /// Accounts:
+    /// | index | writable | signer | description                                                                           |
+    /// |-------|----------|--------|---------------------------------------------------------------------------------------|
+    /// | 0     | ✅       | ❌     | buffer: `authorized_buffer` or `vending_machine_buffer` to migrate                     |
+    /// | 1     | ✅       | ✅     | authority: Authority of an `authorized_buffer`, or any payer; pays for the extra rent |
+    /// | 2     | ❌       | ❌     | system_program: Used to top up the buffer's rent                                       |
+    /// | 3     | ❌       | ❌     | (optional) vending_machine_mint: Mint of a `vending_machine_buffer`                    |
+    Migrate { },
+}
+
+fn build(program_id: &Pubkey, instruction: EchoInstruction, accounts: Vec<AccountMeta>) -> Instruction {
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        // serializing into a Vec can't fail
+        data: instruction.try_to_vec().unwrap(),
+    }
+}
+
+/// Creates an `Echo` instruction.
+pub fn echo(program_id: &Pubkey, echo_buffer: &Pubkey, data: Vec<u8>) -> Instruction {
+    build(
+        program_id,
+        EchoInstruction::Echo { data },
+        vec![AccountMeta::new(*echo_buffer, false)],
+    )
+}
+
+/// Creates an `InitializeAuthorizedEcho` instruction for the buffer at
+/// `AuthorizedBufferHeader::find_address(authority, buffer_seed, program_id)`.
+pub fn initialize_authorized_echo(
+    program_id: &Pubkey,
+    authority: &Pubkey,
+    buffer_seed: u64,
+    buffer_size: usize,
+) -> Instruction {
+    let (authorized_buffer, _) = AuthorizedBufferHeader::find_address(authority, buffer_seed, program_id);
+    build(
+        program_id,
+        EchoInstruction::InitializeAuthorizedEcho { buffer_seed, buffer_size },
+        vec![
+            AccountMeta::new(authorized_buffer, false),
+            AccountMeta::new(*authority, true),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+    )
+}
+
+/// Creates an `AuthorizedEcho` instruction writing to `authority`'s buffer `buffer_seed`.
+pub fn authorized_echo(
+    program_id: &Pubkey,
+    authority: &Pubkey,
+    buffer_seed: u64,
+    data: Vec<u8>,
+) -> Instruction {
+    let (authorized_buffer, _) = AuthorizedBufferHeader::find_address(authority, buffer_seed, program_id);
+    build(
+        program_id,
+        EchoInstruction::AuthorizedEcho { data },
+        vec![
+            AccountMeta::new(authorized_buffer, false),
+            AccountMeta::new_readonly(*authority, true),
+        ],
+    )
+}
+
+/// Creates an `InitializeVendingMachineEcho` instruction for the buffer at
+/// `VendingMachineBufferHeader::find_address(vending_machine_mint, price, program_id)`.
+pub fn initialize_vending_machine_echo(
+    program_id: &Pubkey,
+    vending_machine_mint: &Pubkey,
+    payer: &Pubkey,
+    price: u64,
+    buffer_size: usize,
+) -> Instruction {
+    let (vending_machine_buffer, _) =
+        VendingMachineBufferHeader::find_address(vending_machine_mint, price, program_id);
+    build(
+        program_id,
+        EchoInstruction::InitializeVendingMachineEcho { price, buffer_size },
+        vec![
+            AccountMeta::new(vending_machine_buffer, false),
+            AccountMeta::new_readonly(*vending_machine_mint, false),
+            AccountMeta::new(*payer, true),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+    )
+}
+
+/// Creates a `VendingMachineEcho` instruction paying with `user_token_account`.
+pub fn vending_machine_echo(
+    program_id: &Pubkey,
+    user: &Pubkey,
+    user_token_account: &Pubkey,
+    vending_machine_mint: &Pubkey,
+    price: u64,
+    data: Vec<u8>,
+) -> Instruction {
+    let (vending_machine_buffer, _) =
+        VendingMachineBufferHeader::find_address(vending_machine_mint, price, program_id);
+    build(
+        program_id,
+        EchoInstruction::VendingMachineEcho { data },
+        vec![
+            AccountMeta::new(vending_machine_buffer, false),
+            AccountMeta::new_readonly(*user, true),
+            AccountMeta::new(*user_token_account, false),
+            AccountMeta::new(*vending_machine_mint, false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+        ],
+    )
+}
+
+/// Creates a `Migrate` instruction for `authority`'s buffer `buffer_seed`.
+pub fn migrate(program_id: &Pubkey, authority: &Pubkey, buffer_seed: u64) -> Instruction {
+    let (authorized_buffer, _) = AuthorizedBufferHeader::find_address(authority, buffer_seed, program_id);
+    build(
+        program_id,
+        EchoInstruction::Migrate {},
+        vec![
+            AccountMeta::new(authorized_buffer, false),
+            AccountMeta::new(*authority, true),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+    )
+}
+
+/// Creates a `Migrate` instruction for the vending machine buffer of `vending_machine_mint` at `price`.
+pub fn migrate_vending_machine(
+    program_id: &Pubkey,
+    payer: &Pubkey,
+    vending_machine_mint: &Pubkey,
+    price: u64,
+) -> Instruction {
+    let (vending_machine_buffer, _) =
+        VendingMachineBufferHeader::find_address(vending_machine_mint, price, program_id);
+    build(
+        program_id,
+        EchoInstruction::Migrate {},
+        vec![
+            AccountMeta::new(vending_machine_buffer, false),
+            AccountMeta::new(*payer, true),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new_readonly(*vending_machine_mint, false),
+        ],
+    )
 }
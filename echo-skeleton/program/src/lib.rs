@@ -3,3 +3,4 @@ pub mod error;
 pub mod instruction;
 pub mod processor;
 pub mod state;
+pub mod validation;
@@ -13,22 +13,14 @@ use solana_program::{
 use crate::error::EchoError;
 use crate::instruction::EchoInstruction;
 use crate::state::EchoBuffer;
-use crate::state::AuthorizedBufferHeader;
+use crate::state::{AuthorizedBufferHeader, VendingMachineBufferHeader};
+use crate::validation::{assert_owned_by, assert_pda, assert_signer, assert_with_msg, assert_writable};
 
 // use ::byteorder::{LittleEndian, ReadBytesExt};
 use bytemuck::cast;
 
 use std::cmp;
 
-pub fn assert_with_msg(statement: bool, err: ProgramError, msg: &str) -> ProgramResult {
-    if !statement {
-        msg!(msg);
-        Err(err)
-    } else {
-        Ok(())
-    }
-}
-
 pub struct Processor {}
 
 impl Processor {
@@ -45,6 +37,8 @@ impl Processor {
                 msg!("Instruction: Echo");
                 let accounts_iter = &mut accounts.iter();
                 let account_info = next_account_info(accounts_iter)?;
+                assert_writable(account_info, "First account passed 'Echo Buffer' must be writable.")?;
+
                 msg!("Trying to echo message '{:?}' of length {} onto account '{}'", 
                      data, data.len(), *account_info.key);
 
@@ -81,34 +75,28 @@ impl Processor {
 
                 msg!("Got all three accounts.");
 
-                assert_with_msg(
-                    authority.is_signer,
-                    ProgramError::MissingRequiredSignature,
+                assert_signer(
+                    authority,
                     "Second account passed 'Authority' is not a signer as is required.",
                 )?;
 
                 assert_with_msg(
-                    buffer_size >=9,
+                    buffer_size >= AuthorizedBufferHeader::LEN,
                     ProgramError::InvalidArgument,
-                    "buffer_size must be greater than or equal to 9"
+                    "buffer_size must be greater than or equal to 11"
                 )?;
 
                 msg!("Authority is a signer.");
 
-                let (authorized_buffer_key, bump_seed) = Pubkey::find_program_address(
-                    &[
-                        b"authority",
-                        authority.key.as_ref(),
-                        &buffer_seed.to_le_bytes() // distinguishes different authorized_echo accounts for a single authority
-                    ],
-                    program_id,
-                );
+                // buffer_seed distinguishes different authorized_echo accounts for a single authority
+                let (authorized_buffer_key, bump_seed) =
+                    AuthorizedBufferHeader::find_address(authority.key, buffer_seed, program_id);
                 // bump_seed is used to poke [ bump :) ] PublicKey of PDA off the SecretKey->PublicKey curve
 
                 // authority = owner of buffer we are creating
-                assert_with_msg(
-                    authorized_buffer_key == *authorized_buffer.key,
-                    ProgramError::InvalidArgument,
+                assert_pda(
+                    authorized_buffer,
+                    (authorized_buffer_key, bump_seed),
                     "Key returned from find_program_address (while creating PDA) was not equal to the key passed as the 'authority_buffer' Account.",
                 )?;
 
@@ -127,17 +115,21 @@ impl Processor {
                     &[&[b"authority", authority.key.as_ref(), &buffer_seed.to_le_bytes(),
                         &[bump_seed]]])?;
 
-                let mut ab_buffer = authorized_buffer.data.borrow_mut();
-                ab_buffer[0] = bump_seed;
-                let seed_as_array = buffer_seed.to_le_bytes();
-                ab_buffer[1..9].copy_from_slice(&seed_as_array);
+                *bytemuck::from_bytes_mut(
+                    &mut authorized_buffer.data.borrow_mut()[..AuthorizedBufferHeader::LEN],
+                ) = AuthorizedBufferHeader {
+                    discriminator: AuthorizedBufferHeader::DISCRIMINATOR,
+                    version: AuthorizedBufferHeader::VERSION,
+                    bump_seed,
+                    buffer_seed,
+                };
 
                 Ok(())
             }
             EchoInstruction::AuthorizedEcho { data } => {
                 msg!("Instruction: AuthorizedEcho");
                 // TODO (similar to init echo. ensure proper auth / pda accounts
-                // first 9 bytes = bump seed and buffer seed, remainder to write data
+                // first 11 bytes = header with bump seed and buffer seed, remainder to write data
 
                 let accounts_iter = &mut accounts.iter();
                 let authorized_buffer = next_account_info(accounts_iter)?;
@@ -145,18 +137,28 @@ impl Processor {
 
                 msg!("Got both accounts.");
 
-                assert_with_msg(
-                    authority.is_signer,
-                    ProgramError::MissingRequiredSignature,
+                assert_signer(
+                    authority,
                     "Second account passed 'Authority' is not a signer as is required.",
                 )?;
 
-                let reserved_bytes_for_seeds_on_buffer = 9;
+                assert_writable(
+                    authorized_buffer,
+                    "First account passed 'Authorized Buffer' must be writable.",
+                )?;
+
+                assert_owned_by(
+                    authorized_buffer,
+                    program_id,
+                    "First account passed 'Authorized Buffer' is not owned by the Echo program.",
+                )?;
+
+                let reserved_bytes_for_seeds_on_buffer = AuthorizedBufferHeader::LEN;
 
                 assert_with_msg(
                     authorized_buffer.data_len() >= reserved_bytes_for_seeds_on_buffer,
                     ProgramError::InvalidAccountData,
-                    "First Account 'Authorized Buffer' should have at least 9 bytes already allocated."
+                    "First Account 'Authorized Buffer' should have at least 11 bytes already allocated."
                 )?;
 
                 msg!("Basic checks passed. Verifying auth signs the buffer.");
@@ -165,19 +167,32 @@ impl Processor {
                 // let mut buffer_seed : u64 = 0;
                 // buffer_seed.to_le_bytes().copy_from_slice(&buffer_data[1..9]);
                 {
-                let mut header = AuthorizedBufferHeader::try_from_slice(
-                    &authorized_buffer.data.borrow()[0..9])?;
+                let buffer_data = authorized_buffer.data.borrow();
+                let header: &AuthorizedBufferHeader =
+                    bytemuck::from_bytes(&buffer_data[0..reserved_bytes_for_seeds_on_buffer]);
+
+                assert_with_msg(
+                    header.discriminator == AuthorizedBufferHeader::DISCRIMINATOR,
+                    EchoError::InvalidAccountData.into(),
+                    "First Account 'Authorized Buffer' is not an authorized buffer.",
+                )?;
+
+                assert_with_msg(
+                    header.version == AuthorizedBufferHeader::VERSION,
+                    EchoError::AccountNeedsMigration.into(),
+                    "First Account 'Authorized Buffer' uses an old layout, run Migrate first.",
+                )?;
 
                 let authority_seeds = 
                     &[b"authority", authority.key.as_ref(), &header.buffer_seed.to_le_bytes(), 
                         &[header.bump_seed]];
 
                 msg!("Got auth seeds. Buffer seed '{}', bump seed '{}'. Verifying sign.",
-                    header.buffer_seed, header.bump_seed);
+                    { header.buffer_seed }, header.bump_seed);
                 let auth_key = Pubkey::create_program_address(authority_seeds, program_id)?;
-                assert_with_msg(
-                    auth_key == *authorized_buffer.key,
-                    ProgramError::InvalidArgument,
+                assert_pda(
+                    authorized_buffer,
+                    (auth_key, header.bump_seed),
                     "Invalid PDA seeds for authority",
                 )?;
                 }
@@ -219,32 +234,25 @@ impl Processor {
 
                 msg!("Got all four accounts!");
 
-                assert_with_msg(
-                    payer.is_signer,
-                    ProgramError::MissingRequiredSignature,
+                assert_signer(
+                    payer,
                     "Third account passed 'Payer' is not a signer as is required.",
                 )?;
 
                 assert_with_msg(
-                    buffer_size >=9,
+                    buffer_size >= VendingMachineBufferHeader::LEN,
                     ProgramError::InvalidArgument,
-                    "buffer_size must be greater than or equal to 9"
+                    "buffer_size must be greater than or equal to 11"
                 )?;
 
                 msg!("Payer is a signer and buffer size is good. Creating PDA.");
 
-                let (vending_machine_buffer_key, bump_seed) = Pubkey::find_program_address(
-                    &[
-                        b"vending_machine",
-                        vending_machine_mint.key.as_ref(),
-                        &price.to_le_bytes(),
-                    ],
-                    program_id,
-                );
+                let (vending_machine_buffer_key, bump_seed) =
+                    VendingMachineBufferHeader::find_address(vending_machine_mint.key, price, program_id);
 
-                assert_with_msg(
-                    vending_machine_buffer_key == *vending_machine_buffer.key,
-                    ProgramError::InvalidArgument,
+                assert_pda(
+                    vending_machine_buffer,
+                    (vending_machine_buffer_key, bump_seed),
                     "Key returned from find_program_address (while creating PDA) was not equal to the key passed as the 'vending_machine_buffer' Account.",
                 )?;
 
@@ -261,10 +269,14 @@ impl Processor {
                     &[&[b"vending_machine", vending_machine_mint.key.as_ref(), &price.to_le_bytes(),
                         &[bump_seed]]])?;
 
-                let mut ab_buffer = vending_machine_buffer.data.borrow_mut();
-                ab_buffer[0] = bump_seed;
-                let seed_as_array = price.to_le_bytes();
-                ab_buffer[1..9].copy_from_slice(&seed_as_array);
+                *bytemuck::from_bytes_mut(
+                    &mut vending_machine_buffer.data.borrow_mut()[..VendingMachineBufferHeader::LEN],
+                ) = VendingMachineBufferHeader {
+                    discriminator: VendingMachineBufferHeader::DISCRIMINATOR,
+                    version: VendingMachineBufferHeader::VERSION,
+                    bump_seed,
+                    price,
+                };
 
                 Ok(())
             }
@@ -281,18 +293,17 @@ impl Processor {
 
                 msg!("Got all 5 accounts.");
 
-                assert_with_msg(
-                    user.is_signer,
-                    ProgramError::MissingRequiredSignature,
+                assert_signer(
+                    user,
                     "Second account passed 'User' is not a signer as is required.",
                 )?;
 
-                let reserved_bytes_for_seeds_on_buffer = 9;
+                let reserved_bytes_for_seeds_on_buffer = VendingMachineBufferHeader::LEN;
 
                 assert_with_msg(
                     vending_machine_buffer.data_len() >= reserved_bytes_for_seeds_on_buffer,
                     ProgramError::InvalidAccountData,
-                    "First Account 'Vending Machine Buffer' should have at least 9 bytes already allocated."
+                    "First Account 'Vending Machine Buffer' should have at least 11 bytes already allocated."
                 )?;
 
                 msg!("Basic checks passed. Verifying the user signs the buffer.");
@@ -300,10 +311,124 @@ impl Processor {
 
                 Ok(())
             }
+            EchoInstruction::Migrate { } => {
+                msg!("Instruction: Migrate");
+
+                let accounts_iter = &mut accounts.iter();
+                let buffer = next_account_info(accounts_iter)?;
+                let authority = next_account_info(accounts_iter)?;
+                let system_program = next_account_info(accounts_iter)?;
+                let vending_machine_mint = accounts_iter.next();
+
+                assert_signer(
+                    authority,
+                    "Second account passed 'Authority' is not a signer as is required.",
+                )?;
+
+                assert_owned_by(
+                    buffer,
+                    program_id,
+                    "First account passed 'Buffer' is not owned by the Echo program.",
+                )?;
+
+                // Version 0 headers were the bump followed by the buffer seed or price, so a
+                // buffer is still on version 0 if those bytes derive its address.
+                let (bump_seed, seed) = {
+                    let buffer_data = buffer.data.borrow();
+                    assert_with_msg(
+                        buffer_data.len() >= AuthorizedBufferHeader::V0_LEN,
+                        ProgramError::InvalidAccountData,
+                        "First Account 'Buffer' is too short to hold any header.",
+                    )?;
+                    let mut seed = [0u8; 8];
+                    seed.copy_from_slice(&buffer_data[1..AuthorizedBufferHeader::V0_LEN]);
+                    (buffer_data[0], seed)
+                };
+                let derives_buffer = |seeds: &[&[u8]]| {
+                    Pubkey::create_program_address(seeds, program_id)
+                        .map(|key| key == *buffer.key)
+                        .unwrap_or(false)
+                };
+
+                if derives_buffer(&[b"authority", authority.key.as_ref(), &seed, &[bump_seed]]) {
+                    let header = AuthorizedBufferHeader {
+                        discriminator: AuthorizedBufferHeader::DISCRIMINATOR,
+                        version: AuthorizedBufferHeader::VERSION,
+                        bump_seed,
+                        buffer_seed: u64::from_le_bytes(seed),
+                    };
+                    Self::upgrade_header(
+                        buffer, authority, system_program, AuthorizedBufferHeader::V0_LEN, bytemuck::bytes_of(&header),
+                    )?;
+                    msg!("Migrated authorized buffer to version {}.", AuthorizedBufferHeader::VERSION);
+                    return Ok(());
+                }
+
+                if let Some(mint) = vending_machine_mint {
+                    if derives_buffer(&[b"vending_machine", mint.key.as_ref(), &seed, &[bump_seed]]) {
+                        let header = VendingMachineBufferHeader {
+                            discriminator: VendingMachineBufferHeader::DISCRIMINATOR,
+                            version: VendingMachineBufferHeader::VERSION,
+                            bump_seed,
+                            price: u64::from_le_bytes(seed),
+                        };
+                        Self::upgrade_header(
+                            buffer, authority, system_program, VendingMachineBufferHeader::V0_LEN, bytemuck::bytes_of(&header),
+                        )?;
+                        msg!("Migrated vending machine buffer to version {}.", VendingMachineBufferHeader::VERSION);
+                        return Ok(());
+                    }
+                }
+
+                let is_current = {
+                    let buffer_data = buffer.data.borrow();
+                    buffer_data.len() >= AuthorizedBufferHeader::LEN
+                        && ((buffer_data[0] == AuthorizedBufferHeader::DISCRIMINATOR
+                            && buffer_data[1] == AuthorizedBufferHeader::VERSION)
+                            || (buffer_data[0] == VendingMachineBufferHeader::DISCRIMINATOR
+                                && buffer_data[1] == VendingMachineBufferHeader::VERSION))
+                };
+                assert_with_msg(
+                    is_current,
+                    EchoError::InvalidAccountData.into(),
+                    "First Account 'Buffer' is neither on a known old layout nor on the current one.",
+                )?;
+
+                msg!("Buffer is already on the current layout.");
+                Ok(())
+            }
 
         }
     }
 
+    /// Replaces a version 0 header of `v0_len` bytes with `header`, growing `buffer` to fit
+    /// and shifting the echoed data behind it. `payer` covers the extra rent.
+    fn upgrade_header<'info>(
+        buffer: &AccountInfo<'info>,
+        payer: &AccountInfo<'info>,
+        system_program: &AccountInfo<'info>,
+        v0_len: usize,
+        header: &[u8],
+    ) -> ProgramResult {
+        let old_len = buffer.data_len();
+        let new_len = old_len + header.len() - v0_len;
+        let rent_due = Rent::get()?
+            .minimum_balance(new_len)
+            .saturating_sub(buffer.lamports());
+        if rent_due > 0 {
+            invoke(
+                &system_instruction::transfer(payer.key, buffer.key, rent_due),
+                &[payer.clone(), buffer.clone(), system_program.clone()],
+            )?;
+        }
+        buffer.realloc(new_len, false)?;
+
+        let mut buffer_data = buffer.data.borrow_mut();
+        buffer_data.copy_within(v0_len..old_len, header.len());
+        buffer_data[..header.len()].copy_from_slice(header);
+        Ok(())
+    }
+
     pub fn echo_impl1(
         accounts: &[AccountInfo],
         data: Vec<u8>,
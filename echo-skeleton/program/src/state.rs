@@ -1,17 +1,70 @@
 use borsh::{BorshDeserialize, BorshSerialize};
+use bytemuck::{Pod, Zeroable};
+use solana_program::pubkey::Pubkey;
+use std::mem::size_of;
 
 #[derive(BorshSerialize, BorshDeserialize, Debug, Clone)]
 pub struct EchoBuffer {
     pub data: Vec<u8>,
 }
 
-#[derive(BorshSerialize, BorshDeserialize, Debug, Clone)]
+/// Leads every authorized buffer. The discriminator tells buffer types apart and `version`
+/// is the layout the buffer was written with; echoed data starts right after the header.
+///
+/// Read in place from the buffer, so the layout is fixed: discriminator at 0, version at 1,
+/// bump seed at 2 and the little endian buffer seed at 3..11.
+#[repr(C, packed)]
+#[derive(Debug, Clone, Copy, Pod, Zeroable)]
 pub struct AuthorizedBufferHeader {
+    pub discriminator: u8,
+    pub version: u8,
     pub bump_seed: u8,
     pub buffer_seed: u64,
 }
 
-#[derive(BorshSerialize, BorshDeserialize, Debug, Clone)]
+impl AuthorizedBufferHeader {
+    pub const LEN: usize = size_of::<Self>();
+    pub const DISCRIMINATOR: u8 = 1;
+    pub const VERSION: u8 = 1;
+
+    /// Length of the version 0 header, which was only the bump and buffer seed.
+    pub const V0_LEN: usize = 9;
+
+    /// Address of the authorized buffer `authority` created with `buffer_seed`, the PDA of
+    /// `[b"authority", authority, buffer_seed]`.
+    pub fn find_address(authority: &Pubkey, buffer_seed: u64, program_id: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[b"authority", authority.as_ref(), &buffer_seed.to_le_bytes()],
+            program_id,
+        )
+    }
+}
+
+/// Leads every vending machine buffer, laid out like `AuthorizedBufferHeader` with the little
+/// endian price at 3..11.
+#[repr(C, packed)]
+#[derive(Debug, Clone, Copy, Pod, Zeroable)]
 pub struct VendingMachineBufferHeader {
-    // TODO
+    pub discriminator: u8,
+    pub version: u8,
+    pub bump_seed: u8,
+    pub price: u64,
+}
+
+impl VendingMachineBufferHeader {
+    pub const LEN: usize = size_of::<Self>();
+    pub const DISCRIMINATOR: u8 = 2;
+    pub const VERSION: u8 = 1;
+
+    /// Length of the version 0 header, which was only the bump and price.
+    pub const V0_LEN: usize = 9;
+
+    /// Address of the vending machine buffer for `mint` at `price`, the PDA of
+    /// `[b"vending_machine", mint, price]`.
+    pub fn find_address(mint: &Pubkey, price: u64, program_id: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[b"vending_machine", mint.as_ref(), &price.to_le_bytes()],
+            program_id,
+        )
+    }
 }
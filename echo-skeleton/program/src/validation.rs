@@ -0,0 +1,57 @@
+//! Checks every instruction handler runs its accounts through. Each check logs `msg` when it
+//! fails and returns the error specific to what was wrong.
+
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, msg, program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+use crate::error::EchoError;
+
+pub fn assert_with_msg(statement: bool, err: ProgramError, msg: &str) -> ProgramResult {
+    if !statement {
+        msg!(msg);
+        Err(err)
+    } else {
+        Ok(())
+    }
+}
+
+/// Fails with `MissingRequiredSignature` unless `ai` signed the transaction.
+pub fn assert_signer(ai: &AccountInfo, msg: &str) -> ProgramResult {
+    assert_with_msg(ai.is_signer, ProgramError::MissingRequiredSignature, msg)
+}
+
+/// Fails with `AccountMustBeWritable` unless `ai` was passed as writable.
+pub fn assert_writable(ai: &AccountInfo, msg: &str) -> ProgramResult {
+    assert_with_msg(ai.is_writable, EchoError::AccountMustBeWritable.into(), msg)
+}
+
+/// Fails with `InvalidAccountOwner` unless `ai` is owned by `owner`.
+pub fn assert_owned_by(ai: &AccountInfo, owner: &Pubkey, msg: &str) -> ProgramResult {
+    assert_with_msg(ai.owner == owner, EchoError::InvalidAccountOwner.into(), msg)
+}
+
+/// Fails with `InvalidAccountAddress` unless `ai` is the `expected` account.
+pub fn assert_key(ai: &AccountInfo, expected: &Pubkey, msg: &str) -> ProgramResult {
+    assert_with_msg(ai.key == expected, EchoError::InvalidAccountAddress.into(), msg)
+}
+
+/// Fails with `InvalidProgramAddress` unless `ai` is the PDA found by a `find_address` style
+/// helper. Returns the bump seed so the caller can sign for the PDA.
+pub fn assert_pda(ai: &AccountInfo, (expected, bump): (Pubkey, u8), msg: &str) -> Result<u8, ProgramError> {
+    assert_with_msg(*ai.key == expected, EchoError::InvalidProgramAddress.into(), msg)?;
+    Ok(bump)
+}
+
+/// Fails with `DuplicateAccount` if any account appears twice in `accounts`.
+pub fn assert_unique(accounts: &[&AccountInfo], msg: &str) -> ProgramResult {
+    for (i, ai) in accounts.iter().enumerate() {
+        assert_with_msg(
+            !accounts[..i].iter().any(|other| other.key == ai.key),
+            EchoError::DuplicateAccount.into(),
+            msg,
+        )?;
+    }
+    Ok(())
+}
@@ -23,8 +23,8 @@ class AuthorizedBuffer(NamedTuple):
     def parse(data):
         decoded = base64.b64decode(data)
         print(len(decoded))
-        bump_seed, buffer_seed = struct.unpack("<BQ", decoded[0:9])
-        text = decoded[9:].decode("ascii").rstrip("\0")
+        _, version, bump_seed, buffer_seed = struct.unpack("<BBBQ", decoded[0:11])
+        text = decoded[11:].decode("ascii").rstrip("\0")
         return AuthorizedBuffer(bump_seed=bump_seed, buffer_seed=buffer_seed, text=text)
 
 
@@ -0,0 +1,79 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    account_info::{AccountInfo, next_account_info},
+    entrypoint::ProgramResult, msg,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+use exchangebooth::{
+    cpi::{self, CpiContext, Exchange},
+    token::unpack_token_account,
+};
+
+#[cfg(not(feature = "no-entrypoint"))]
+use solana_program::entrypoint;
+#[cfg(not(feature = "no-entrypoint"))]
+entrypoint!(process_instruction);
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone)]
+pub struct Swap {
+    pub amount: u64,
+    pub min_amount_out: u64,
+}
+
+/// Swaps `amount` through a booth and checks that the booth's `ExchangeResult` matches what
+/// arrived in the destination account.
+///
+/// Accounts:
+/// | index | description                                     |
+/// |-------|-------------------------------------------------|
+/// | 0     | booth_program                                   |
+/// | 1..   | the accounts of `Exchange`, in the same order   |
+pub fn process_instruction(
+    _program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    instruction_data: &[u8],
+) -> ProgramResult {
+    let Swap { amount, min_amount_out } = Swap::try_from_slice(instruction_data)
+        .map_err(|_| ProgramError::InvalidInstructionData)?;
+
+    let accounts_iter = &mut accounts.iter();
+    let booth_program = next_account_info(accounts_iter)?;
+    let exchange_accounts = Exchange {
+        user: next_account_info(accounts_iter)?.clone(),
+        user_source: next_account_info(accounts_iter)?.clone(),
+        user_destination: next_account_info(accounts_iter)?.clone(),
+        exchange_booth: next_account_info(accounts_iter)?.clone(),
+        vault_a: next_account_info(accounts_iter)?.clone(),
+        vault_b: next_account_info(accounts_iter)?.clone(),
+        mint_a: next_account_info(accounts_iter)?.clone(),
+        mint_b: next_account_info(accounts_iter)?.clone(),
+        oracle: next_account_info(accounts_iter)?.clone(),
+        token_program: next_account_info(accounts_iter)?.clone(),
+        protocol_config: next_account_info(accounts_iter)?.clone(),
+        user_volume: next_account_info(accounts_iter)?.clone(),
+        system_program: next_account_info(accounts_iter)?.clone(),
+        access: next_account_info(accounts_iter)?.clone(),
+        referrer: accounts_iter.next().cloned(),
+    };
+
+    let destination = exchange_accounts.user_destination.clone();
+    let balance_before = unpack_token_account(&destination)?.amount;
+
+    let result = cpi::exchange(
+        CpiContext::new(booth_program.clone(), exchange_accounts),
+        amount,
+        min_amount_out,
+    )?;
+
+    let received = unpack_token_account(&destination)?.amount - balance_before;
+    if received != result.amount_out {
+        msg!("Booth reported {} out but {} arrived", result.amount_out, received);
+        return Err(ProgramError::InvalidAccountData);
+    }
+
+    msg!("Swapped {} for {} (fee {})", result.amount_in, result.amount_out, result.fee);
+
+    Ok(())
+}
@@ -0,0 +1,146 @@
+use borsh::BorshSerialize;
+use solana_program::{
+    instruction::{AccountMeta, Instruction},
+    program_option::COption,
+    program_pack::Pack,
+    pubkey::Pubkey,
+    rent::Rent,
+    system_program,
+};
+use solana_program_test::{processor, tokio, ProgramTest};
+use solana_sdk::{
+    account::Account,
+    signature::{Keypair, Signer},
+    transaction::Transaction,
+};
+use spl_token::state::{Account as TokenAccount, AccountState, Mint};
+
+use booth_swap_example::Swap;
+use exchangebooth::{
+    instruction::{self, BoothKeys},
+    processor::Processor,
+    state::{ExchangeBooth, OraclePrice},
+};
+
+fn mint_account(decimals: u8) -> Account {
+    let mut data = vec![0; Mint::LEN];
+    Mint {
+        mint_authority: COption::Some(Pubkey::new_unique()),
+        supply: 1_000_000_000,
+        decimals,
+        is_initialized: true,
+        freeze_authority: COption::None,
+    }
+    .pack_into_slice(&mut data);
+    Account { lamports: Rent::default().minimum_balance(Mint::LEN), data, owner: spl_token::id(), ..Account::default() }
+}
+
+fn token_account(mint: &Pubkey, owner: &Pubkey, amount: u64) -> Account {
+    let mut data = vec![0; TokenAccount::LEN];
+    TokenAccount {
+        mint: *mint,
+        owner: *owner,
+        amount,
+        state: AccountState::Initialized,
+        ..TokenAccount::default()
+    }
+    .pack_into_slice(&mut data);
+    Account {
+        lamports: Rent::default().minimum_balance(TokenAccount::LEN),
+        data,
+        owner: spl_token::id(),
+        ..Account::default()
+    }
+}
+
+fn system_account() -> Account {
+    Account { lamports: 10_000_000_000, owner: system_program::id(), ..Account::default() }
+}
+
+#[tokio::test]
+async fn swaps_through_a_booth_by_cpi() {
+    let booth_program_id = Pubkey::new_unique();
+    let swap_program_id = Pubkey::new_unique();
+    let mut program_test = ProgramTest::new(
+        "booth_swap_example",
+        swap_program_id,
+        processor!(booth_swap_example::process_instruction),
+    );
+    program_test.add_program("exchangebooth", booth_program_id, processor!(Processor::process_instruction));
+
+    let admin = Keypair::new();
+    let user = Keypair::new();
+    let mint_a = Pubkey::new_unique();
+    let mint_b = Pubkey::new_unique();
+    let oracle = Pubkey::new_unique();
+    let exchange_booth = Pubkey::new_unique();
+    let admin_b = Pubkey::new_unique();
+    let user_a = Pubkey::new_unique();
+    let user_b = Pubkey::new_unique();
+
+    program_test.add_account(admin.pubkey(), system_account());
+    program_test.add_account(user.pubkey(), system_account());
+    program_test.add_account(mint_a, mint_account(6));
+    program_test.add_account(mint_b, mint_account(6));
+    program_test.add_account(admin_b, token_account(&mint_b, &admin.pubkey(), 1_000_000));
+    program_test.add_account(user_a, token_account(&mint_a, &user.pubkey(), 1_000));
+    program_test.add_account(user_b, token_account(&mint_b, &user.pubkey(), 0));
+
+    // one A is worth two B, behind the oracle buffer's header
+    let mut oracle_data = vec![0; OraclePrice::OFFSET];
+    oracle_data.extend(OraclePrice { price: 2, decimals: 0 }.try_to_vec().unwrap());
+    program_test.add_account(oracle, Account { lamports: 1_000_000_000, data: oracle_data, ..Account::default() });
+
+    program_test.add_account(
+        exchange_booth,
+        Account {
+            lamports: Rent::default().minimum_balance(ExchangeBooth::LEN),
+            data: vec![0; ExchangeBooth::LEN],
+            owner: booth_program_id,
+            ..Account::default()
+        },
+    );
+
+    let (mut banks_client, payer, recent_blockhash) = program_test.start().await;
+
+    let (vault_b, _) = ExchangeBooth::find_vault_address(&exchange_booth, b"vault_b", &booth_program_id);
+    let setup = Transaction::new_signed_with_payer(
+        &[
+            instruction::initialize_exchange_booth(
+                &booth_program_id, &admin.pubkey(), &exchange_booth, &mint_a, &mint_b, &oracle, &spl_token::id(), 100, 0, 0, 0, 0,
+            ),
+            instruction::deposit(
+                &booth_program_id, &admin.pubkey(), &admin_b, &vault_b, &mint_b, &exchange_booth, &spl_token::id(), 1_000_000,
+            ),
+        ],
+        Some(&payer.pubkey()),
+        &[&payer, &admin],
+        recent_blockhash,
+    );
+    banks_client.process_transaction(setup).await.unwrap();
+
+    let booth = BoothKeys { exchange_booth, mint_a, mint_b, oracle, token_program: spl_token::id() };
+    let access = instruction::allowlist_access(&booth_program_id, &exchange_booth, &user.pubkey());
+    let exchange_ix = instruction::exchange(
+        &booth_program_id, &user.pubkey(), &user_a, &user_b, &booth, &access, None, 100, 0,
+    );
+    let mut accounts = vec![AccountMeta::new_readonly(booth_program_id, false)];
+    accounts.extend(exchange_ix.accounts);
+    let swap = Transaction::new_signed_with_payer(
+        &[Instruction {
+            program_id: swap_program_id,
+            accounts,
+            data: Swap { amount: 100, min_amount_out: 0 }.try_to_vec().unwrap(),
+        }],
+        Some(&payer.pubkey()),
+        &[&payer, &user],
+        recent_blockhash,
+    );
+    banks_client.process_transaction(swap).await.unwrap();
+
+    // 100 A is 200 B, less the 1% spread
+    let user_b_account = banks_client.get_account(user_b).await.unwrap().unwrap();
+    assert_eq!(TokenAccount::unpack(&user_b_account.data).unwrap().amount, 198);
+    let user_a_account = banks_client.get_account(user_a).await.unwrap().unwrap();
+    assert_eq!(TokenAccount::unpack(&user_a_account.data).unwrap().amount, 900);
+}
@@ -0,0 +1,77 @@
+//! Creating and resizing the accounts the program derives and owns.
+
+use solana_program::{
+    account_info::AccountInfo, entrypoint::ProgramResult, program::{invoke, invoke_signed},
+    program_error::ProgramError, pubkey::Pubkey, system_instruction,
+    sysvar::{rent::Rent, Sysvar},
+};
+
+/// Creates the PDA `account` with `space` bytes and `lamports`, owned by `owner`, signing with
+/// `signer_seeds`. Anyone can send lamports to a PDA before it exists, which would make
+/// `create_account` fail, so an account that already has some is topped up, allocated and
+/// assigned instead.
+pub fn create_pda_account<'info>(
+    payer: &AccountInfo<'info>,
+    account: &AccountInfo<'info>,
+    lamports: u64,
+    space: usize,
+    owner: &Pubkey,
+    system_program: &AccountInfo<'info>,
+    signer_seeds: &[&[u8]],
+) -> ProgramResult {
+    let current = account.lamports();
+    if current == 0 {
+        return invoke_signed(
+            &system_instruction::create_account(payer.key, account.key, lamports, space as u64, owner),
+            &[payer.clone(), account.clone(), system_program.clone()],
+            &[signer_seeds],
+        );
+    }
+
+    if lamports > current {
+        invoke(
+            &system_instruction::transfer(payer.key, account.key, lamports - current),
+            &[payer.clone(), account.clone(), system_program.clone()],
+        )?;
+    }
+    invoke_signed(
+        &system_instruction::allocate(account.key, space as u64),
+        &[account.clone(), system_program.clone()],
+        &[signer_seeds],
+    )?;
+    invoke_signed(
+        &system_instruction::assign(account.key, owner),
+        &[account.clone(), system_program.clone()],
+        &[signer_seeds],
+    )
+}
+
+/// Reallocates the program owned `account` to `new_len` bytes, topping up its rent from `payer`.
+pub fn grow<'info>(
+    payer: &AccountInfo<'info>,
+    account: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    new_len: usize,
+) -> ProgramResult {
+    let rent_due = Rent::get()?.minimum_balance(new_len).saturating_sub(account.lamports());
+    if rent_due > 0 {
+        invoke(
+            &system_instruction::transfer(payer.key, account.key, rent_due),
+            &[payer.clone(), account.clone(), system_program.clone()],
+        )?;
+    }
+    account.realloc(new_len, false)
+}
+
+/// Reallocates the program owned `account` down to `new_len` bytes and moves the rent it no
+/// longer needs to `receiver`.
+pub fn shrink(account: &AccountInfo, receiver: &AccountInfo, new_len: usize) -> ProgramResult {
+    account.realloc(new_len, false)?;
+    let surplus = account.lamports().saturating_sub(Rent::get()?.minimum_balance(new_len));
+    **account.lamports.borrow_mut() -= surplus;
+    **receiver.lamports.borrow_mut() = receiver
+        .lamports()
+        .checked_add(surplus)
+        .ok_or(ProgramError::InvalidArgument)?;
+    Ok(())
+}
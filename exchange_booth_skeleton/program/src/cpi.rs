@@ -0,0 +1,233 @@
+use borsh::BorshSerialize;
+use solana_program::{
+    account_info::AccountInfo,
+    entrypoint::ProgramResult,
+    instruction::{AccountMeta, Instruction},
+    program::invoke_signed,
+    program_error::ProgramError,
+};
+
+use crate::instruction::{ExchangeBoothInstruction, ExchangeResult, QuoteResult};
+
+/// The booth program, the accounts of one instruction and the seeds of any PDA signing for
+/// the caller. Build one and hand it to `cpi::exchange` and friends, e.g.
+/// `cpi::exchange(CpiContext::new(booth_program, Exchange { .. }), amount, min_amount_out)`.
+pub struct CpiContext<'a, 'b, 'c, 'info, T> {
+    pub program: AccountInfo<'info>,
+    pub accounts: T,
+    pub signer_seeds: &'a [&'b [&'c [u8]]],
+}
+
+impl<'a, 'b, 'c, 'info, T> CpiContext<'a, 'b, 'c, 'info, T> {
+    pub fn new(program: AccountInfo<'info>, accounts: T) -> Self {
+        Self { program, accounts, signer_seeds: &[] }
+    }
+
+    pub fn new_with_signer(program: AccountInfo<'info>, accounts: T, signer_seeds: &'a [&'b [&'c [u8]]]) -> Self {
+        Self { program, accounts, signer_seeds }
+    }
+}
+
+/// Accounts of `Exchange` and `Quote`, in instruction order.
+#[derive(Clone)]
+pub struct Exchange<'info> {
+    pub user: AccountInfo<'info>,
+    pub user_source: AccountInfo<'info>,
+    pub user_destination: AccountInfo<'info>,
+    pub exchange_booth: AccountInfo<'info>,
+    pub vault_a: AccountInfo<'info>,
+    pub vault_b: AccountInfo<'info>,
+    pub mint_a: AccountInfo<'info>,
+    pub mint_b: AccountInfo<'info>,
+    pub oracle: AccountInfo<'info>,
+    pub token_program: AccountInfo<'info>,
+    pub protocol_config: AccountInfo<'info>,
+    pub user_volume: AccountInfo<'info>,
+    pub system_program: AccountInfo<'info>,
+    pub access: AccountInfo<'info>,
+    pub referrer: Option<AccountInfo<'info>>,
+}
+
+impl<'info> Exchange<'info> {
+    fn account_infos(&self) -> Vec<AccountInfo<'info>> {
+        let mut infos = vec![
+            self.user.clone(),
+            self.user_source.clone(),
+            self.user_destination.clone(),
+            self.exchange_booth.clone(),
+            self.vault_a.clone(),
+            self.vault_b.clone(),
+            self.mint_a.clone(),
+            self.mint_b.clone(),
+            self.oracle.clone(),
+            self.token_program.clone(),
+            self.protocol_config.clone(),
+            self.user_volume.clone(),
+            self.system_program.clone(),
+            self.access.clone(),
+        ];
+        infos.extend(self.referrer.clone());
+        infos
+    }
+
+    fn account_metas(&self) -> Vec<AccountMeta> {
+        let mut metas = vec![
+            AccountMeta::new(*self.user.key, true),
+            AccountMeta::new(*self.user_source.key, false),
+            AccountMeta::new(*self.user_destination.key, false),
+            AccountMeta::new(*self.exchange_booth.key, false),
+            AccountMeta::new(*self.vault_a.key, false),
+            AccountMeta::new(*self.vault_b.key, false),
+            AccountMeta::new_readonly(*self.mint_a.key, false),
+            AccountMeta::new_readonly(*self.mint_b.key, false),
+            AccountMeta::new_readonly(*self.oracle.key, false),
+            AccountMeta::new_readonly(*self.token_program.key, false),
+            AccountMeta::new_readonly(*self.protocol_config.key, false),
+            AccountMeta::new(*self.user_volume.key, false),
+            AccountMeta::new_readonly(*self.system_program.key, false),
+            AccountMeta::new_readonly(*self.access.key, false),
+        ];
+        metas.extend(self.referrer.as_ref().map(|referrer| AccountMeta::new(*referrer.key, false)));
+        metas
+    }
+}
+
+/// One booth of a `RouteExchange`, with the user's volume and access accounts for it.
+#[derive(Clone)]
+pub struct RouteHop<'info> {
+    pub exchange_booth: AccountInfo<'info>,
+    pub vault_a: AccountInfo<'info>,
+    pub vault_b: AccountInfo<'info>,
+    pub mint_a: AccountInfo<'info>,
+    pub mint_b: AccountInfo<'info>,
+    pub oracle: AccountInfo<'info>,
+    pub user_volume: AccountInfo<'info>,
+    pub access: AccountInfo<'info>,
+}
+
+/// Accounts of `RouteExchange`, in instruction order.
+#[derive(Clone)]
+pub struct RouteExchange<'info> {
+    pub user: AccountInfo<'info>,
+    pub user_source: AccountInfo<'info>,
+    pub user_destination: AccountInfo<'info>,
+    pub token_program: AccountInfo<'info>,
+    pub protocol_config: AccountInfo<'info>,
+    pub system_program: AccountInfo<'info>,
+    pub hops: Vec<RouteHop<'info>>,
+}
+
+impl<'info> RouteExchange<'info> {
+    fn account_infos(&self) -> Vec<AccountInfo<'info>> {
+        let mut infos = vec![
+            self.user.clone(),
+            self.user_source.clone(),
+            self.user_destination.clone(),
+            self.token_program.clone(),
+            self.protocol_config.clone(),
+            self.system_program.clone(),
+        ];
+        for hop in &self.hops {
+            infos.extend([
+                hop.exchange_booth.clone(),
+                hop.vault_a.clone(),
+                hop.vault_b.clone(),
+                hop.mint_a.clone(),
+                hop.mint_b.clone(),
+                hop.oracle.clone(),
+                hop.user_volume.clone(),
+                hop.access.clone(),
+            ]);
+        }
+        infos
+    }
+
+    fn account_metas(&self) -> Vec<AccountMeta> {
+        let mut metas = vec![
+            AccountMeta::new(*self.user.key, true),
+            AccountMeta::new(*self.user_source.key, false),
+            AccountMeta::new(*self.user_destination.key, false),
+            AccountMeta::new_readonly(*self.token_program.key, false),
+            AccountMeta::new_readonly(*self.protocol_config.key, false),
+            AccountMeta::new_readonly(*self.system_program.key, false),
+        ];
+        for hop in &self.hops {
+            metas.extend([
+                AccountMeta::new(*hop.exchange_booth.key, false),
+                AccountMeta::new(*hop.vault_a.key, false),
+                AccountMeta::new(*hop.vault_b.key, false),
+                AccountMeta::new_readonly(*hop.mint_a.key, false),
+                AccountMeta::new_readonly(*hop.mint_b.key, false),
+                AccountMeta::new_readonly(*hop.oracle.key, false),
+                AccountMeta::new(*hop.user_volume.key, false),
+                AccountMeta::new_readonly(*hop.access.key, false),
+            ]);
+        }
+        metas
+    }
+}
+
+fn invoke_booth<'info, T>(
+    ctx: &CpiContext<'_, '_, '_, 'info, T>,
+    instruction: ExchangeBoothInstruction,
+    accounts: Vec<AccountMeta>,
+    mut account_infos: Vec<AccountInfo<'info>>,
+) -> ProgramResult {
+    let ix = Instruction {
+        program_id: *ctx.program.key,
+        accounts,
+        data: instruction.try_to_vec()?,
+    };
+    account_infos.push(ctx.program.clone());
+    invoke_signed(&ix, &account_infos, ctx.signer_seeds)
+}
+
+/// Swaps through one booth and returns what the booth reported.
+pub fn exchange<'info>(
+    ctx: CpiContext<'_, '_, '_, 'info, Exchange<'info>>,
+    tokens_to_transfer: u64,
+    min_amount_out: u64,
+) -> Result<ExchangeResult, ProgramError> {
+    invoke_booth(
+        &ctx,
+        ExchangeBoothInstruction::Exchange { tokens_to_transfer, min_amount_out },
+        ctx.accounts.account_metas(),
+        ctx.accounts.account_infos(),
+    )?;
+    ExchangeResult::from_return_data(ctx.program.key).ok_or(ProgramError::InvalidAccountData)
+}
+
+/// Prices a swap through one booth without moving funds. The referrer, if any, is ignored.
+pub fn quote<'info>(
+    ctx: CpiContext<'_, '_, '_, 'info, Exchange<'info>>,
+    tokens_to_transfer: u64,
+) -> Result<QuoteResult, ProgramError> {
+    let mut accounts = ctx.accounts.clone();
+    accounts.referrer = None;
+    let metas = accounts
+        .account_metas()
+        .into_iter()
+        .map(|meta| AccountMeta::new_readonly(meta.pubkey, false))
+        .collect();
+    invoke_booth(
+        &ctx,
+        ExchangeBoothInstruction::Quote { tokens_to_transfer },
+        metas,
+        accounts.account_infos(),
+    )?;
+    QuoteResult::from_return_data(ctx.program.key).ok_or(ProgramError::InvalidAccountData)
+}
+
+/// Swaps through every hop of the route.
+pub fn route_exchange<'info>(
+    ctx: CpiContext<'_, '_, '_, 'info, RouteExchange<'info>>,
+    amount_in: u64,
+    min_amount_out: u64,
+) -> ProgramResult {
+    invoke_booth(
+        &ctx,
+        ExchangeBoothInstruction::RouteExchange { amount_in, min_amount_out },
+        ctx.accounts.account_metas(),
+        ctx.accounts.account_infos(),
+    )
+}
@@ -24,6 +24,40 @@ pub enum ExchangeBoothError {
     InvalidInstructionInput,
     #[error("Invalid account data.")]
     InvalidAccountData,
+    #[error("Booth registry is full.")]
+    RegistryFull,
+    #[error("Booth is not in the registry.")]
+    BoothNotRegistered,
+    #[error("Amount out is below the requested minimum.")]
+    SlippageExceeded,
+    #[error("Flash loan is not repaid in the same transaction.")]
+    FlashLoanNotRepaid,
+    #[error("A flash loan is already outstanding.")]
+    FlashLoanOutstanding,
+    #[error("Not enough multisig signers.")]
+    NotEnoughSigners,
+    #[error("Exchange Booth is paused.")]
+    Paused,
+    #[error("Config change is still timelocked.")]
+    ConfigChangeTimelocked,
+    #[error("Volume limit exceeded.")]
+    VolumeLimitExceeded,
+    #[error("User is not allowed to exchange on this booth.")]
+    AccessDenied,
+    #[error("Withdrawal limit exceeded.")]
+    WithdrawalLimitExceeded,
+    #[error("Queued withdrawal is still timelocked.")]
+    WithdrawalTimelocked,
+    #[error("The same account was passed more than once.")]
+    DuplicateAccount,
+    #[error("Account uses an old layout, run Migrate first.")]
+    AccountNeedsMigration,
+    #[error("Mint uses a token extension the booth does not support.")]
+    UnsupportedMintExtension,
+    #[error("Exchange Booth has no native SOL side.")]
+    NoNativeSide,
+    #[error("Amount does not fit in a token amount.")]
+    AmountOverflow,
 }
 
 impl From<ExchangeBoothError> for ProgramError {
@@ -0,0 +1,101 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{log::sol_log_data, pubkey::Pubkey};
+
+use crate::state::ConfigChange;
+
+/// Events the program logs through `sol_log_data`, one Borsh encoded `BoothEvent` per
+/// "Program data:" log line.
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+pub enum BoothEvent {
+    Initialized {
+        exchange_booth: Pubkey,
+        admin: Pubkey,
+        mint_a: Pubkey,
+        mint_b: Pubkey,
+        oracle: Pubkey,
+        spread_bps: u64,
+        flash_loan_fee_bps: u64,
+        referral_share_bps: u64,
+    },
+    Deposited {
+        exchange_booth: Pubkey,
+        depositor: Pubkey,
+        vault: Pubkey,
+        amount: u64,
+    },
+    Withdrawn {
+        exchange_booth: Pubkey,
+        vault: Pubkey,
+        destination: Pubkey,
+        amount: u64,
+    },
+    /// One booth's part of an `Exchange`, or one hop of a `RouteExchange`. `fee` is in the
+    /// output token and includes `referral_fee`.
+    Exchanged {
+        exchange_booth: Pubkey,
+        user: Pubkey,
+        input_mint: Pubkey,
+        output_mint: Pubkey,
+        amount_in: u64,
+        amount_out: u64,
+        fee: u64,
+        referrer: Option<Pubkey>, // token account paid `referral_fee`
+        referral_fee: u64,
+    },
+    Closed {
+        exchange_booth: Pubkey,
+        admin: Pubkey,
+    },
+    ConfigChanged {
+        exchange_booth: Pubkey,
+        change: ConfigChange,
+    },
+}
+
+impl BoothEvent {
+    pub fn emit(&self) {
+        // serializing into a Vec can't fail
+        sol_log_data(&[&self.try_to_vec().unwrap()]);
+    }
+
+    /// Decodes the events `program_id` logged in a transaction's log messages. Data logged
+    /// by other programs, including ones the booth invokes, is skipped.
+    pub fn decode_logs<S: AsRef<str>>(program_id: &Pubkey, logs: &[S]) -> Vec<BoothEvent> {
+        let program_id = program_id.to_string();
+        let mut invoke_stack: Vec<&str> = Vec::new();
+        let mut events = Vec::new();
+
+        for log in logs {
+            let log = log.as_ref();
+            if let Some(rest) = log.strip_prefix("Program data: ") {
+                if invoke_stack.last() != Some(&program_id.as_str()) {
+                    continue;
+                }
+                // sol_log_data logs each field as its own base64 string
+                for field in rest.split(' ') {
+                    if let Some(event) = base64::decode(field)
+                        .ok()
+                        .and_then(|bytes| BoothEvent::try_from_slice(&bytes).ok())
+                    {
+                        events.push(event);
+                    }
+                }
+            } else if let Some(rest) = log.strip_prefix("Program ") {
+                // "Program log: ..." and friends don't name a program
+                let mut words = rest.split(' ');
+                let program = words.next().unwrap_or_default();
+                if program.parse::<Pubkey>().is_err() {
+                    continue;
+                }
+                match words.next() {
+                    Some("invoke") => invoke_stack.push(program),
+                    Some("success") | Some("failed:") => {
+                        invoke_stack.pop();
+                    }
+                    _ => {}
+                }
+            }
+        }
+        events
+    }
+}
@@ -1,21 +1,1137 @@
 use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{
+    bpf_loader_upgradeable,
+    instruction::{AccountMeta, Instruction},
+    program::get_return_data,
+    pubkey::Pubkey,
+    system_program, sysvar,
+};
+
+use crate::{
+    state::{
+        AdminMultisig, AllowlistEntry, BoothRegistry, ConfigChange, ExchangeBooth, OraclePrice,
+        ProtocolConfig, QueuedConfigChange, QueuedWithdrawal, RegistryIndex, UserVolume,
+    },
+    token,
+};
 
 #[derive(BorshSerialize, BorshDeserialize, Debug, Clone)]
 pub enum ExchangeBoothInstruction {
-
+    /// Creates both vaults, writes the booth config and appends the booth to the registry of
+    /// its mint pair. The admin pays for the registry growing by one key, or for creating it
+    /// and listing it in the registry index when this is the pair's first booth.
+    ///
+    /// Both mints must belong to `token_program`, either the legacy token program or
+    /// Token-2022. Of the Token-2022 extensions only transfer fees, metadata, groups and
+    /// their pointers are allowed; mints with any other extension are refused.
+    ///
+    /// `withdrawal_limit` caps direct withdrawals per vault and `withdrawal_window_slots`; with a
+    /// limit of 0 every withdrawal has to be queued.
+    ///
+    /// Accounts:
+    /// | index | writable | signer | description                                         |
+    /// |-------|----------|--------|-----------------------------------------------------|
+    /// | 0     | ✅       | ✅     | admin: Pays for the vaults and owns the booth        |
+    /// | 1     | ✅       | ❌     | exchange_booth: Zeroed `ExchangeBooth::LEN` byte account owned by the program |
+    /// | 2     | ❌       | ❌     | mint_a                                               |
+    /// | 3     | ❌       | ❌     | mint_b                                               |
+    /// | 4     | ✅       | ❌     | vault_a: PDA of `[b"vault_a", exchange_booth]`       |
+    /// | 5     | ✅       | ❌     | vault_b: PDA of `[b"vault_b", exchange_booth]`       |
+    /// | 6     | ❌       | ❌     | oracle                                               |
+    /// | 7     | ❌       | ❌     | system_program                                       |
+    /// | 8     | ❌       | ❌     | token_program                                        |
+    /// | 9     | ❌       | ❌     | rent sysvar                                          |
+    /// | 10    | ✅       | ❌     | registry: PDA of `[b"registry", mint_a, mint_b]`, mints in ascending order |
+    /// | 11    | ✅       | ❌     | registry_index: PDA of `[b"registry_index"]`         |
     InititializeExchangeBooth {
-        // TODO
+        spread_bps: u64,
+        flash_loan_fee_bps: u64,
+        referral_share_bps: u64,
+        withdrawal_limit: u64,
+        withdrawal_window_slots: u64,
      },
+    /// Moves `amount` tokens from the depositor's token account into one of the booth's vaults.
+    ///
+    /// To deposit lamports into the native SOL side, pass the depositor's wrapped SOL PDA
+    /// (`token::find_wrapped_sol_address`) as `depositor_token_account`. It is opened with
+    /// `amount` lamports of the depositor and closed again once the tokens are in the vault, which
+    /// takes the system program as a trailing account.
+    ///
+    /// Accounts:
+    /// | index | writable | signer | description                                 |
+    /// |-------|----------|--------|---------------------------------------------|
+    /// | 0     | ✅       | ✅     | depositor: Owner of `depositor_token_account` |
+    /// | 1     | ✅       | ❌     | depositor_token_account                      |
+    /// | 2     | ✅       | ❌     | vault: vault_a or vault_b                    |
+    /// | 3     | ❌       | ❌     | mint: The vault's mint                       |
+    /// | 4     | ❌       | ❌     | exchange_booth                               |
+    /// | 5     | ❌       | ❌     | token_program                                |
+    /// | 6     | ❌       | ❌     | (optional) system_program, for lamports      |
     Deposit {
-        // TODO
+        amount: u64,
     },
+    /// Sends `amount_to_withdraw` from one of the vaults to the admin's token account. Protocol
+    /// fees owed to the treasury can not be withdrawn.
+    ///
+    /// At most `withdrawal_limit` can leave each vault per `withdrawal_window_slots`. Larger
+    /// amounts, and every amount on a booth whose limit is 0, need `QueueWithdrawal`.
+    ///
+    /// Accounts:
+    /// | index | writable | signer | description                              |
+    /// |-------|----------|--------|------------------------------------------|
+    /// | 0     | ❌       | ✅     | admin: Signer, or the booth's multisig PDA |
+    /// | 1     | ✅       | ❌     | admins_token_account                      |
+    /// | 2     | ✅       | ❌     | vault: vault_a or vault_b                 |
+    /// | 3     | ❌       | ❌     | mint                                      |
+    /// | 4     | ✅       | ❌     | exchange_booth                            |
+    /// | 5     | ❌       | ❌     | token_program                             |
+    /// | 6..   | ❌       | ✅     | multisig signers, if admin is a multisig  |
     Withdraw {
         amount_to_withdraw: u64,
     },
+    /// Swaps `tokens_to_transfer` of the source token for the other token of the booth at the
+    /// oracle price less the booth spread. The mint of `user_source` picks the direction.
+    ///
+    /// An optional trailing referrer token account for the output token receives
+    /// `referral_share_bps` of the fee.
+    ///
+    /// Volume counts against the booth's `epoch_cap` and, through the user's volume PDA,
+    /// against `per_user_epoch_cap`. The user pays for the volume PDA the first time a
+    /// per-user cap applies.
+    ///
+    /// On an access gated booth the access account must be the user's allowlist entry or a
+    /// token account of the user holding at least `gating_min_balance` of `gating_mint`.
+    /// Otherwise any account can be passed there.
+    ///
+    /// With Token-2022 transfer fees the swap is priced on what reaches the input vault, and
+    /// `min_amount_out` is checked against what reaches `user_destination`.
+    ///
+    /// On a booth with a native SOL side the user can pass their wrapped SOL PDA
+    /// (`token::find_wrapped_sol_address`) as `user_source` or `user_destination` to pay in
+    /// or get paid in lamports. The booth opens it for the exchange and closes it into the
+    /// user's system account at the end.
+    ///
+    /// Sets an `ExchangeResult` as return data for programs calling in through CPI.
+    ///
+    /// Accounts:
+    /// | index | writable | signer | description                                  |
+    /// |-------|----------|--------|----------------------------------------------|
+    /// | 0     | ✅       | ✅     | user                                          |
+    /// | 1     | ✅       | ❌     | user_source: Token account paying into the booth |
+    /// | 2     | ✅       | ❌     | user_destination: Token account receiving the output |
+    /// | 3     | ✅       | ❌     | exchange_booth                                |
+    /// | 4     | ✅       | ❌     | vault_a                                       |
+    /// | 5     | ✅       | ❌     | vault_b                                       |
+    /// | 6     | ❌       | ❌     | mint_a                                        |
+    /// | 7     | ❌       | ❌     | mint_b                                        |
+    /// | 8     | ❌       | ❌     | oracle                                        |
+    /// | 9     | ❌       | ❌     | token_program                                 |
+    /// | 10    | ❌       | ❌     | protocol_config: PDA of `[b"protocol_config"]` |
+    /// | 11    | ✅       | ❌     | user_volume: PDA of `[b"user_volume", exchange_booth, user]` |
+    /// | 12    | ❌       | ❌     | system_program                                |
+    /// | 13    | ❌       | ❌     | access: PDA of `[b"allowlist", exchange_booth, user]` or gating token account |
+    /// | 14    | ✅       | ❌     | (optional) referrer token account             |
     Exchange {
-        tokens_to_transfer: u64
+        tokens_to_transfer: u64,
+        min_amount_out: u64,
     },
+    /// Closes both (empty) vaults, removes the booth from its pair's registry and sends all
+    /// rent to `rent_receiver`, which the admin picks since a multisig admin can't spend
+    /// lamports. Transfer fees withheld in Token-2022 vaults have to be harvested to the mint
+    /// first.
+    ///
+    /// Accounts:
+    /// | index | writable | signer | description                           |
+    /// |-------|----------|--------|---------------------------------------|
+    /// | 0     | ❌       | ✅     | admin                                  |
+    /// | 1     | ✅       | ❌     | exchange_booth                         |
+    /// | 2     | ✅       | ❌     | vault_a                                |
+    /// | 3     | ✅       | ❌     | vault_b                                |
+    /// | 4     | ✅       | ❌     | registry: PDA of `[b"registry", mint_a, mint_b]`, mints in ascending order |
+    /// | 5     | ❌       | ❌     | token_program                          |
+    /// | 6     | ✅       | ❌     | rent_receiver                          |
+    /// | 7..   | ❌       | ✅     | multisig signers, if admin is a multisig |
     CloseExchangeBooth {
         // TODO
     },
+    /// Swaps through several booths in one instruction. Each hop's output is paid straight
+    /// into the next booth's input vault, and `min_amount_out` is checked against the last hop.
+    ///
+    /// Accounts:
+    /// | index   | writable | signer | description                                           |
+    /// |---------|----------|--------|-------------------------------------------------------|
+    /// | 0       | ✅       | ✅     | user                                                   |
+    /// | 1       | ✅       | ❌     | user_source: Token account paying into the first booth |
+    /// | 2       | ✅       | ❌     | user_destination: Token account receiving the output   |
+    /// | 3       | ❌       | ❌     | token_program                                          |
+    /// | 4       | ❌       | ❌     | protocol_config: PDA of `[b"protocol_config"]`         |
+    /// | 5       | ❌       | ❌     | system_program                                         |
+    /// | 6 + 8n  | ✅       | ❌     | exchange_booth of hop n                                |
+    /// | 7 + 8n  | ✅       | ❌     | vault_a of hop n                                       |
+    /// | 8 + 8n  | ✅       | ❌     | vault_b of hop n                                       |
+    /// | 9 + 8n  | ❌       | ❌     | mint_a of hop n                                        |
+    /// | 10 + 8n | ❌       | ❌     | mint_b of hop n                                        |
+    /// | 11 + 8n | ❌       | ❌     | oracle of hop n                                        |
+    /// | 12 + 8n | ✅       | ❌     | user_volume of hop n                                   |
+    /// | 13 + 8n | ❌       | ❌     | access account of hop n, as in `Exchange`              |
+    RouteExchange {
+        amount_in: u64,
+        min_amount_out: u64,
+    },
+    /// Lends `amount` out of one of the booth's vaults. A `FlashRepay` for the same booth and
+    /// vault of at least `amount` plus `flash_loan_fee_bps` must appear later in the transaction.
+    ///
+    /// Accounts:
+    /// | index | writable | signer | description                                   |
+    /// |-------|----------|--------|-----------------------------------------------|
+    /// | 0     | ❌       | ✅     | borrower                                       |
+    /// | 1     | ✅       | ❌     | borrower_token_account: Receives the loan      |
+    /// | 2     | ✅       | ❌     | exchange_booth                                 |
+    /// | 3     | ✅       | ❌     | vault: vault_a or vault_b of the booth         |
+    /// | 4     | ❌       | ❌     | mint: The vault's mint                         |
+    /// | 5     | ❌       | ❌     | token_program                                  |
+    /// | 6     | ❌       | ❌     | instructions sysvar                            |
+    FlashBorrow {
+        amount: u64,
+    },
+    /// Pays back the outstanding flash loan of a booth. What reaches the vault after any
+    /// transfer fee has to cover principal plus fee.
+    ///
+    /// Accounts:
+    /// | index | writable | signer | description                                   |
+    /// |-------|----------|--------|-----------------------------------------------|
+    /// | 0     | ❌       | ✅     | borrower                                       |
+    /// | 1     | ✅       | ❌     | borrower_token_account: Pays back the loan     |
+    /// | 2     | ✅       | ❌     | exchange_booth                                 |
+    /// | 3     | ✅       | ❌     | vault: The vault the loan was taken from       |
+    /// | 4     | ❌       | ❌     | mint: The vault's mint                         |
+    /// | 5     | ❌       | ❌     | token_program                                  |
+    FlashRepay {
+        amount: u64,
+    },
+    /// Creates or updates the global protocol config. Only the program's upgrade authority can
+    /// create the PDA; after that only the current treasury owner can change it.
+    ///
+    /// Accounts:
+    /// | index | writable | signer | description                                        |
+    /// |-------|----------|--------|----------------------------------------------------|
+    /// | 0     | ✅       | ✅     | authority: Upgrade authority on creation, treasury owner after |
+    /// | 1     | ✅       | ❌     | protocol_config: PDA of `[b"protocol_config"]`       |
+    /// | 2     | ❌       | ❌     | system_program                                       |
+    /// | 3     | ❌       | ❌     | program_data: The program's ProgramData, read on creation |
+    SetProtocolConfig {
+        treasury_owner: Pubkey,
+        protocol_fee_share_bps: u64,
+    },
+    /// Sends the protocol's share of fees from every listed booth to the treasury.
+    ///
+    /// Accounts:
+    /// | index  | writable | signer | description                                     |
+    /// |--------|----------|--------|-------------------------------------------------|
+    /// | 0      | ❌       | ✅     | treasury_owner                                   |
+    /// | 1      | ❌       | ❌     | protocol_config: PDA of `[b"protocol_config"]`   |
+    /// | 2      | ❌       | ❌     | token_program                                    |
+    /// | 3 + 7n | ✅       | ❌     | exchange_booth of booth n                        |
+    /// | 4 + 7n | ✅       | ❌     | vault_a of booth n                               |
+    /// | 5 + 7n | ✅       | ❌     | vault_b of booth n                               |
+    /// | 6 + 7n | ❌       | ❌     | mint_a of booth n                                |
+    /// | 7 + 7n | ❌       | ❌     | mint_b of booth n                                |
+    /// | 8 + 7n | ✅       | ❌     | treasury token account for mint_a of booth n     |
+    /// | 9 + 7n | ✅       | ❌     | treasury token account for mint_b of booth n     |
+    CollectProtocolFees {
+    },
+    /// Proposes a new admin for the booth. Nothing changes until the new admin accepts.
+    /// Proposing the default pubkey cancels a pending proposal.
+    ///
+    /// Accounts:
+    /// | index | writable | signer | description          |
+    /// |-------|----------|--------|----------------------|
+    /// | 0     | ❌       | ✅     | admin                 |
+    /// | 1     | ✅       | ❌     | exchange_booth        |
+    /// | 2..   | ❌       | ✅     | multisig signers, if admin is a multisig |
+    ProposeAdmin {
+        new_admin: Pubkey,
+    },
+    /// Completes an admin transfer started with `ProposeAdmin`.
+    ///
+    /// Accounts:
+    /// | index | writable | signer | description                     |
+    /// |-------|----------|--------|---------------------------------|
+    /// | 0     | ❌       | ✅     | pending_admin: The proposed admin |
+    /// | 1     | ✅       | ❌     | exchange_booth                   |
+    /// | 2..   | ❌       | ✅     | multisig signers, if pending_admin is a multisig |
+    AcceptAdmin {
+    },
+    /// Hands the booth to an M-of-N multisig. The signer set is stored in the
+    /// `[b"multisig", exchange_booth]` PDA, which becomes the booth's admin. Calling it again
+    /// through the multisig replaces the signer set.
+    ///
+    /// Accounts:
+    /// | index | writable | signer | description                                          |
+    /// |-------|----------|--------|------------------------------------------------------|
+    /// | 0     | ❌       | ✅     | admin: Signer, or the booth's multisig PDA             |
+    /// | 1     | ✅       | ❌     | exchange_booth                                         |
+    /// | 2     | ✅       | ❌     | multisig: PDA of `[b"multisig", exchange_booth]`       |
+    /// | 3     | ✅       | ✅     | payer: Funds the multisig account on creation          |
+    /// | 4     | ❌       | ❌     | system_program                                         |
+    /// | 5..   | ❌       | ✅     | multisig signers, if admin is already a multisig       |
+    SetAdminMultisig {
+        signers: Vec<Pubkey>,
+        threshold: u8,
+    },
+    /// Pauses or resumes parts of the booth. `pause_flags` is a combination of the
+    /// `state::PAUSE_*` bits for deposits, withdrawals and exchanges.
+    ///
+    /// Accounts:
+    /// | index | writable | signer | description                               |
+    /// |-------|----------|--------|-------------------------------------------|
+    /// | 0     | ❌       | ✅     | admin: Signer, or the booth's multisig PDA  |
+    /// | 1     | ✅       | ❌     | exchange_booth                             |
+    /// | 2..   | ❌       | ✅     | multisig signers, if admin is a multisig   |
+    SetPauseFlags {
+        pause_flags: u8,
+    },
+    /// Queues a booth parameter change in the `[b"config_change", exchange_booth, change_id]`
+    /// PDA. `earliest_apply_slot` must be at least `MIN_CONFIG_CHANGE_DELAY_SLOTS` away.
+    ///
+    /// Accounts:
+    /// | index | writable | signer | description                                              |
+    /// |-------|----------|--------|----------------------------------------------------------|
+    /// | 0     | ❌       | ✅     | admin: Signer, or the booth's multisig PDA                 |
+    /// | 1     | ❌       | ❌     | exchange_booth                                             |
+    /// | 2     | ✅       | ❌     | queued_change: PDA of `[b"config_change", exchange_booth, change_id]` |
+    /// | 3     | ✅       | ✅     | payer: Funds the queued change account                     |
+    /// | 4     | ❌       | ❌     | system_program                                             |
+    /// | 5..   | ❌       | ✅     | multisig signers, if admin is a multisig                   |
+    QueueConfigChange {
+        change_id: u64,
+        change: ConfigChange,
+        earliest_apply_slot: u64,
+    },
+    /// Applies a queued change once its slot is reached. Callable by anyone.
+    ///
+    /// Accounts:
+    /// | index | writable | signer | description                                  |
+    /// |-------|----------|--------|----------------------------------------------|
+    /// | 0     | ✅       | ❌     | exchange_booth                                |
+    /// | 1     | ✅       | ❌     | queued_change                                 |
+    /// | 2     | ✅       | ❌     | payer: Gets the queued change's rent back     |
+    ApplyConfigChange {
+    },
+    /// Drops a queued change before it is applied.
+    ///
+    /// Accounts:
+    /// | index | writable | signer | description                                  |
+    /// |-------|----------|--------|----------------------------------------------|
+    /// | 0     | ❌       | ✅     | admin: Signer, or the booth's multisig PDA     |
+    /// | 1     | ❌       | ❌     | exchange_booth                                |
+    /// | 2     | ✅       | ❌     | queued_change                                 |
+    /// | 3     | ✅       | ❌     | payer: Gets the queued change's rent back     |
+    /// | 4..   | ❌       | ✅     | multisig signers, if admin is a multisig      |
+    CancelConfigChange {
+    },
+    /// Adds `user` to or removes it from the booth's allowlist. Removing closes the entry and
+    /// refunds its rent to the payer.
+    ///
+    /// Accounts:
+    /// | index | writable | signer | description                                  |
+    /// |-------|----------|--------|----------------------------------------------|
+    /// | 0     | ❌       | ✅     | admin: Signer, or the booth's multisig PDA     |
+    /// | 1     | ❌       | ❌     | exchange_booth                                |
+    /// | 2     | ✅       | ❌     | allowlist_entry: PDA of `[b"allowlist", exchange_booth, user]` |
+    /// | 3     | ✅       | ✅     | payer                                         |
+    /// | 4     | ❌       | ❌     | system_program                                |
+    /// | 5..   | ❌       | ✅     | multisig signers, if admin is a multisig      |
+    SetAllowlistEntry {
+        user: Pubkey,
+        allowed: bool,
+    },
+    /// Queues a withdrawal of `amount` from a vault to `destination`, bypassing the booth's
+    /// withdrawal limit. It can be executed `MIN_WITHDRAWAL_DELAY_SLOTS` after queueing.
+    ///
+    /// Accounts:
+    /// | index | writable | signer | description                                  |
+    /// |-------|----------|--------|----------------------------------------------|
+    /// | 0     | ❌       | ✅     | admin: Signer, or the booth's multisig PDA     |
+    /// | 1     | ❌       | ❌     | exchange_booth                                |
+    /// | 2     | ✅       | ❌     | queued_withdrawal: PDA of `[b"withdrawal", exchange_booth, withdrawal_id]` |
+    /// | 3     | ❌       | ❌     | vault: vault_a or vault_b                     |
+    /// | 4     | ❌       | ❌     | destination: Token account to withdraw to     |
+    /// | 5     | ✅       | ✅     | payer                                         |
+    /// | 6     | ❌       | ❌     | system_program                                |
+    /// | 7..   | ❌       | ✅     | multisig signers, if admin is a multisig      |
+    QueueWithdrawal {
+        withdrawal_id: u64,
+        amount: u64,
+    },
+    /// Executes a queued withdrawal once its delay has passed. Callable by anyone.
+    ///
+    /// Accounts:
+    /// | index | writable | signer | description                                  |
+    /// |-------|----------|--------|----------------------------------------------|
+    /// | 0     | ❌       | ❌     | exchange_booth                                |
+    /// | 1     | ✅       | ❌     | queued_withdrawal                             |
+    /// | 2     | ✅       | ❌     | vault                                         |
+    /// | 3     | ❌       | ❌     | mint: The vault's mint                        |
+    /// | 4     | ✅       | ❌     | destination                                   |
+    /// | 5     | ✅       | ❌     | payer: Gets the queued withdrawal's rent back  |
+    /// | 6     | ❌       | ❌     | token_program                                 |
+    ExecuteWithdrawal {
+    },
+    /// Drops a queued withdrawal before it is executed.
+    ///
+    /// Accounts:
+    /// | index | writable | signer | description                                  |
+    /// |-------|----------|--------|----------------------------------------------|
+    /// | 0     | ❌       | ✅     | admin: Signer, or the booth's multisig PDA     |
+    /// | 1     | ❌       | ❌     | exchange_booth                                |
+    /// | 2     | ✅       | ❌     | queued_withdrawal                             |
+    /// | 3     | ✅       | ❌     | payer: Gets the queued withdrawal's rent back  |
+    /// | 4..   | ❌       | ✅     | multisig signers, if admin is a multisig      |
+    CancelWithdrawal {
+    },
+    /// Upgrades an Exchange Booth from before booths had a header to the current layout in
+    /// place, reallocating it and adding it to the registry of its mint pair. The payer covers
+    /// the extra rent. Booths that are already current are left alone and any other account is
+    /// refused. Callable by anyone.
+    ///
+    /// Migrated booths start with a `withdrawal_limit` of 0, so their withdrawals have to be
+    /// queued until one is set.
+    ///
+    /// Accounts:
+    /// | index | writable | signer | description                                  |
+    /// |-------|----------|--------|----------------------------------------------|
+    /// | 0     | ✅       | ✅     | payer                                         |
+    /// | 1     | ✅       | ❌     | exchange_booth: Booth to migrate              |
+    /// | 2     | ❌       | ❌     | system_program                                |
+    /// | 3     | ✅       | ❌     | registry: PDA of `[b"registry", mint_a, mint_b]`, mints in ascending order |
+    /// | 4     | ✅       | ❌     | registry_index: PDA of `[b"registry_index"]`     |
+    Migrate {
+    },
+    /// Prices `tokens_to_transfer` the way `Exchange` would, without moving funds or counting
+    /// volume, and sets a `QuoteResult` as return data. Meant to be simulated.
+    ///
+    /// Takes the same accounts as `Exchange`; the user doesn't have to sign and nothing needs
+    /// to be writable. Pause flags and the access gate are checked, volume caps are not.
+    Quote {
+        tokens_to_transfer: u64,
+    },
+}
+
+/// Return data of `Exchange`. `amount_out` is what reached the user's destination account,
+/// after any transfer fee. `fee` is in the output token and includes any referral fee.
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Copy, PartialEq)]
+pub struct ExchangeResult {
+    pub amount_in: u64,
+    pub amount_out: u64,
+    pub fee: u64,
+    pub price: OraclePrice, // oracle price the exchange was priced at
+}
+
+impl ExchangeResult {
+    /// Reads the result of an `Exchange` CPI into `program_id` back out of the return data.
+    /// Returns `None` if the return data was set by another program or isn't an
+    /// `ExchangeResult`.
+    pub fn from_return_data(program_id: &Pubkey) -> Option<Self> {
+        read_return_data(program_id)
+    }
+}
+
+/// Return data of `Quote`. `price_impact_bps` is how far `amount_out` falls short of the
+/// output at the plain oracle price.
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Copy, PartialEq)]
+pub struct QuoteResult {
+    pub amount_in: u64,
+    pub amount_out: u64,
+    pub fee: u64,
+    pub price: OraclePrice,
+    pub price_impact_bps: u64,
+}
+
+impl QuoteResult {
+    /// Like `ExchangeResult::from_return_data`, for a `Quote` CPI.
+    pub fn from_return_data(program_id: &Pubkey) -> Option<Self> {
+        read_return_data(program_id)
+    }
+}
+
+fn read_return_data<T: BorshDeserialize>(program_id: &Pubkey) -> Option<T> {
+    let (setter, data) = get_return_data()?;
+    if setter != *program_id {
+        return None;
+    }
+    T::try_from_slice(&data).ok()
+}
+
+/// The keys of one booth that `exchange`, `quote` and `route_exchange` need. Its vaults are
+/// derived from `exchange_booth`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BoothKeys {
+    pub exchange_booth: Pubkey,
+    pub mint_a: Pubkey,
+    pub mint_b: Pubkey,
+    pub oracle: Pubkey,
+    pub token_program: Pubkey, // owner of both mints, `spl_token` or `spl_token_2022`
+}
+
+impl BoothKeys {
+    fn account_metas(&self, program_id: &Pubkey) -> Vec<AccountMeta> {
+        let (vault_a, _) = ExchangeBooth::find_vault_address(&self.exchange_booth, b"vault_a", program_id);
+        let (vault_b, _) = ExchangeBooth::find_vault_address(&self.exchange_booth, b"vault_b", program_id);
+        vec![
+            AccountMeta::new(self.exchange_booth, false),
+            AccountMeta::new(vault_a, false),
+            AccountMeta::new(vault_b, false),
+            AccountMeta::new_readonly(self.mint_a, false),
+            AccountMeta::new_readonly(self.mint_b, false),
+            AccountMeta::new_readonly(self.oracle, false),
+        ]
+    }
+}
+
+fn build(program_id: &Pubkey, instruction: ExchangeBoothInstruction, accounts: Vec<AccountMeta>) -> Instruction {
+    Instruction {
+        program_id: *program_id,
+        accounts,
+        // serializing into a Vec can't fail
+        data: instruction.try_to_vec().unwrap(),
+    }
+}
+
+/// An admin signs itself, unless it is a multisig PDA whose `signers` follow the accounts.
+fn admin_metas(admin: &Pubkey, accounts: Vec<AccountMeta>, signers: &[&Pubkey]) -> Vec<AccountMeta> {
+    std::iter::once(AccountMeta::new_readonly(*admin, signers.is_empty()))
+        .chain(accounts)
+        .chain(signers.iter().map(|signer| AccountMeta::new_readonly(**signer, true)))
+        .collect()
+}
+
+/// Creates an `InititializeExchangeBooth` instruction. `exchange_booth` must already be a
+/// zeroed `ExchangeBooth::LEN` byte account owned by the program.
+#[allow(clippy::too_many_arguments)]
+pub fn initialize_exchange_booth(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    exchange_booth: &Pubkey,
+    mint_a: &Pubkey,
+    mint_b: &Pubkey,
+    oracle: &Pubkey,
+    token_program: &Pubkey,
+    spread_bps: u64,
+    flash_loan_fee_bps: u64,
+    referral_share_bps: u64,
+    withdrawal_limit: u64,
+    withdrawal_window_slots: u64,
+) -> Instruction {
+    let (vault_a, _) = ExchangeBooth::find_vault_address(exchange_booth, b"vault_a", program_id);
+    let (vault_b, _) = ExchangeBooth::find_vault_address(exchange_booth, b"vault_b", program_id);
+    let (registry, _) = BoothRegistry::find_address(mint_a, mint_b, program_id);
+    let (registry_index, _) = RegistryIndex::find_address(program_id);
+    build(
+        program_id,
+        ExchangeBoothInstruction::InititializeExchangeBooth {
+            spread_bps,
+            flash_loan_fee_bps,
+            referral_share_bps,
+            withdrawal_limit,
+            withdrawal_window_slots,
+        },
+        vec![
+            AccountMeta::new(*admin, true),
+            AccountMeta::new(*exchange_booth, false),
+            AccountMeta::new_readonly(*mint_a, false),
+            AccountMeta::new_readonly(*mint_b, false),
+            AccountMeta::new(vault_a, false),
+            AccountMeta::new(vault_b, false),
+            AccountMeta::new_readonly(*oracle, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new_readonly(*token_program, false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+            AccountMeta::new(registry, false),
+            AccountMeta::new(registry_index, false),
+        ],
+    )
+}
+
+/// Creates a `Deposit` instruction. The system program is only passed for the native mint.
+#[allow(clippy::too_many_arguments)]
+pub fn deposit(
+    program_id: &Pubkey,
+    depositor: &Pubkey,
+    depositor_token_account: &Pubkey,
+    vault: &Pubkey,
+    mint: &Pubkey,
+    exchange_booth: &Pubkey,
+    token_program: &Pubkey,
+    amount: u64,
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(*depositor, true),
+        AccountMeta::new(*depositor_token_account, false),
+        AccountMeta::new(*vault, false),
+        AccountMeta::new_readonly(*mint, false),
+        AccountMeta::new_readonly(*exchange_booth, false),
+        AccountMeta::new_readonly(*token_program, false),
+    ];
+    if token::is_native_mint(mint) {
+        accounts.push(AccountMeta::new_readonly(system_program::id(), false));
+    }
+    build(program_id, ExchangeBoothInstruction::Deposit { amount }, accounts)
+}
+
+/// Creates a `Withdraw` instruction. Pass the multisig `signers` if `admin` is a multisig.
+#[allow(clippy::too_many_arguments)]
+pub fn withdraw(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    admins_token_account: &Pubkey,
+    vault: &Pubkey,
+    mint: &Pubkey,
+    exchange_booth: &Pubkey,
+    token_program: &Pubkey,
+    signers: &[&Pubkey],
+    amount_to_withdraw: u64,
+) -> Instruction {
+    build(
+        program_id,
+        ExchangeBoothInstruction::Withdraw { amount_to_withdraw },
+        admin_metas(
+            admin,
+            vec![
+                AccountMeta::new(*admins_token_account, false),
+                AccountMeta::new(*vault, false),
+                AccountMeta::new_readonly(*mint, false),
+                AccountMeta::new(*exchange_booth, false),
+                AccountMeta::new_readonly(*token_program, false),
+            ],
+            signers,
+        ),
+    )
+}
+
+fn exchange_metas(
+    program_id: &Pubkey,
+    user: &Pubkey,
+    user_source: &Pubkey,
+    user_destination: &Pubkey,
+    booth: &BoothKeys,
+    access: &Pubkey,
+) -> Vec<AccountMeta> {
+    let (protocol_config, _) = ProtocolConfig::find_address(program_id);
+    let (user_volume, _) = UserVolume::find_address(&booth.exchange_booth, user, program_id);
+    let mut accounts = vec![
+        AccountMeta::new(*user, true),
+        AccountMeta::new(*user_source, false),
+        AccountMeta::new(*user_destination, false),
+    ];
+    accounts.extend(booth.account_metas(program_id));
+    accounts.extend([
+        AccountMeta::new_readonly(booth.token_program, false),
+        AccountMeta::new_readonly(protocol_config, false),
+        AccountMeta::new(user_volume, false),
+        AccountMeta::new_readonly(system_program::id(), false),
+        AccountMeta::new_readonly(*access, false),
+    ]);
+    accounts
+}
+
+/// Creates an `Exchange` instruction. `access` is the user's allowlist entry, which works for
+/// booths that aren't access gated too, or a gating token account of the user.
+#[allow(clippy::too_many_arguments)]
+pub fn exchange(
+    program_id: &Pubkey,
+    user: &Pubkey,
+    user_source: &Pubkey,
+    user_destination: &Pubkey,
+    booth: &BoothKeys,
+    access: &Pubkey,
+    referrer: Option<&Pubkey>,
+    tokens_to_transfer: u64,
+    min_amount_out: u64,
+) -> Instruction {
+    let mut accounts = exchange_metas(program_id, user, user_source, user_destination, booth, access);
+    if let Some(referrer) = referrer {
+        accounts.push(AccountMeta::new(*referrer, false));
+    }
+    build(
+        program_id,
+        ExchangeBoothInstruction::Exchange { tokens_to_transfer, min_amount_out },
+        accounts,
+    )
+}
+
+/// Creates a `Quote` instruction for simulating an `exchange` with the same arguments.
+pub fn quote(
+    program_id: &Pubkey,
+    user: &Pubkey,
+    user_source: &Pubkey,
+    user_destination: &Pubkey,
+    booth: &BoothKeys,
+    access: &Pubkey,
+    tokens_to_transfer: u64,
+) -> Instruction {
+    let accounts = exchange_metas(program_id, user, user_source, user_destination, booth, access)
+        .into_iter()
+        .map(|meta| AccountMeta::new_readonly(meta.pubkey, false))
+        .collect();
+    build(
+        program_id,
+        ExchangeBoothInstruction::Quote { tokens_to_transfer },
+        accounts,
+    )
+}
+
+/// Address of the user's allowlist entry, the usual `access` account.
+pub fn allowlist_access(program_id: &Pubkey, exchange_booth: &Pubkey, user: &Pubkey) -> Pubkey {
+    AllowlistEntry::find_address(exchange_booth, user, program_id).0
+}
+
+/// Creates a `CloseExchangeBooth` instruction that sends the booth's rent to `rent_receiver`.
+pub fn close_exchange_booth(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    booth: &BoothKeys,
+    rent_receiver: &Pubkey,
+    signers: &[&Pubkey],
+) -> Instruction {
+    let BoothKeys { exchange_booth, mint_a, mint_b, token_program, .. } = booth;
+    let (vault_a, _) = ExchangeBooth::find_vault_address(exchange_booth, b"vault_a", program_id);
+    let (vault_b, _) = ExchangeBooth::find_vault_address(exchange_booth, b"vault_b", program_id);
+    let (registry, _) = BoothRegistry::find_address(mint_a, mint_b, program_id);
+    build(
+        program_id,
+        ExchangeBoothInstruction::CloseExchangeBooth {},
+        admin_metas(
+            admin,
+            vec![
+                AccountMeta::new(*exchange_booth, false),
+                AccountMeta::new(vault_a, false),
+                AccountMeta::new(vault_b, false),
+                AccountMeta::new(registry, false),
+                AccountMeta::new_readonly(*token_program, false),
+                AccountMeta::new(*rent_receiver, false),
+            ],
+            signers,
+        ),
+    )
+}
+
+/// Creates a `RouteExchange` instruction through `hops`, each a booth and the user's access
+/// account for it as in `exchange`. All hops must use the first hop's token program.
+pub fn route_exchange(
+    program_id: &Pubkey,
+    user: &Pubkey,
+    user_source: &Pubkey,
+    user_destination: &Pubkey,
+    hops: &[(BoothKeys, Pubkey)],
+    amount_in: u64,
+    min_amount_out: u64,
+) -> Instruction {
+    let (protocol_config, _) = ProtocolConfig::find_address(program_id);
+    let token_program = hops.first().map(|(booth, _)| booth.token_program).unwrap_or_else(spl_token::id);
+    let mut accounts = vec![
+        AccountMeta::new(*user, true),
+        AccountMeta::new(*user_source, false),
+        AccountMeta::new(*user_destination, false),
+        AccountMeta::new_readonly(token_program, false),
+        AccountMeta::new_readonly(protocol_config, false),
+        AccountMeta::new_readonly(system_program::id(), false),
+    ];
+    for (booth, access) in hops {
+        let (user_volume, _) = UserVolume::find_address(&booth.exchange_booth, user, program_id);
+        accounts.extend(booth.account_metas(program_id));
+        accounts.push(AccountMeta::new(user_volume, false));
+        accounts.push(AccountMeta::new_readonly(*access, false));
+    }
+    build(
+        program_id,
+        ExchangeBoothInstruction::RouteExchange { amount_in, min_amount_out },
+        accounts,
+    )
+}
+
+/// Creates a `FlashBorrow` instruction. The transaction needs a matching `flash_repay`.
+#[allow(clippy::too_many_arguments)]
+pub fn flash_borrow(
+    program_id: &Pubkey,
+    borrower: &Pubkey,
+    borrower_token_account: &Pubkey,
+    exchange_booth: &Pubkey,
+    vault: &Pubkey,
+    mint: &Pubkey,
+    token_program: &Pubkey,
+    amount: u64,
+) -> Instruction {
+    build(
+        program_id,
+        ExchangeBoothInstruction::FlashBorrow { amount },
+        vec![
+            AccountMeta::new_readonly(*borrower, true),
+            AccountMeta::new(*borrower_token_account, false),
+            AccountMeta::new(*exchange_booth, false),
+            AccountMeta::new(*vault, false),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new_readonly(*token_program, false),
+            AccountMeta::new_readonly(sysvar::instructions::id(), false),
+        ],
+    )
+}
+
+/// Creates a `FlashRepay` instruction.
+#[allow(clippy::too_many_arguments)]
+pub fn flash_repay(
+    program_id: &Pubkey,
+    borrower: &Pubkey,
+    borrower_token_account: &Pubkey,
+    exchange_booth: &Pubkey,
+    vault: &Pubkey,
+    mint: &Pubkey,
+    token_program: &Pubkey,
+    amount: u64,
+) -> Instruction {
+    build(
+        program_id,
+        ExchangeBoothInstruction::FlashRepay { amount },
+        vec![
+            AccountMeta::new_readonly(*borrower, true),
+            AccountMeta::new(*borrower_token_account, false),
+            AccountMeta::new(*exchange_booth, false),
+            AccountMeta::new(*vault, false),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new_readonly(*token_program, false),
+        ],
+    )
+}
+
+/// Creates a `SetProtocolConfig` instruction.
+pub fn set_protocol_config(
+    program_id: &Pubkey,
+    authority: &Pubkey,
+    treasury_owner: &Pubkey,
+    protocol_fee_share_bps: u64,
+) -> Instruction {
+    let (protocol_config, _) = ProtocolConfig::find_address(program_id);
+    let (program_data, _) = Pubkey::find_program_address(&[program_id.as_ref()], &bpf_loader_upgradeable::id());
+    build(
+        program_id,
+        ExchangeBoothInstruction::SetProtocolConfig { treasury_owner: *treasury_owner, protocol_fee_share_bps },
+        vec![
+            AccountMeta::new(*authority, true),
+            AccountMeta::new(protocol_config, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new_readonly(program_data, false),
+        ],
+    )
+}
+
+/// Creates a `CollectProtocolFees` instruction. Each of `booths` is a booth, its mint_a and
+/// mint_b, and the treasury token accounts for those mints.
+pub fn collect_protocol_fees(
+    program_id: &Pubkey,
+    treasury_owner: &Pubkey,
+    token_program: &Pubkey,
+    booths: &[(Pubkey, Pubkey, Pubkey, Pubkey, Pubkey)],
+) -> Instruction {
+    let (protocol_config, _) = ProtocolConfig::find_address(program_id);
+    let mut accounts = vec![
+        AccountMeta::new_readonly(*treasury_owner, true),
+        AccountMeta::new_readonly(protocol_config, false),
+        AccountMeta::new_readonly(*token_program, false),
+    ];
+    for (exchange_booth, mint_a, mint_b, treasury_a, treasury_b) in booths {
+        let (vault_a, _) = ExchangeBooth::find_vault_address(exchange_booth, b"vault_a", program_id);
+        let (vault_b, _) = ExchangeBooth::find_vault_address(exchange_booth, b"vault_b", program_id);
+        accounts.extend([
+            AccountMeta::new(*exchange_booth, false),
+            AccountMeta::new(vault_a, false),
+            AccountMeta::new(vault_b, false),
+            AccountMeta::new_readonly(*mint_a, false),
+            AccountMeta::new_readonly(*mint_b, false),
+            AccountMeta::new(*treasury_a, false),
+            AccountMeta::new(*treasury_b, false),
+        ]);
+    }
+    build(program_id, ExchangeBoothInstruction::CollectProtocolFees {}, accounts)
+}
+
+/// Creates a `ProposeAdmin` instruction.
+pub fn propose_admin(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    exchange_booth: &Pubkey,
+    signers: &[&Pubkey],
+    new_admin: &Pubkey,
+) -> Instruction {
+    build(
+        program_id,
+        ExchangeBoothInstruction::ProposeAdmin { new_admin: *new_admin },
+        admin_metas(admin, vec![AccountMeta::new(*exchange_booth, false)], signers),
+    )
+}
+
+/// Creates an `AcceptAdmin` instruction.
+pub fn accept_admin(
+    program_id: &Pubkey,
+    pending_admin: &Pubkey,
+    exchange_booth: &Pubkey,
+    signers: &[&Pubkey],
+) -> Instruction {
+    build(
+        program_id,
+        ExchangeBoothInstruction::AcceptAdmin {},
+        admin_metas(pending_admin, vec![AccountMeta::new(*exchange_booth, false)], signers),
+    )
+}
+
+/// Creates a `SetAdminMultisig` instruction. `signers` are the current multisig signers if
+/// `admin` is already a multisig, `new_signers` the signer set to store.
+pub fn set_admin_multisig(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    exchange_booth: &Pubkey,
+    payer: &Pubkey,
+    signers: &[&Pubkey],
+    new_signers: Vec<Pubkey>,
+    threshold: u8,
+) -> Instruction {
+    let (multisig, _) = AdminMultisig::find_address(exchange_booth, program_id);
+    build(
+        program_id,
+        ExchangeBoothInstruction::SetAdminMultisig { signers: new_signers, threshold },
+        admin_metas(
+            admin,
+            vec![
+                AccountMeta::new(*exchange_booth, false),
+                AccountMeta::new(multisig, false),
+                AccountMeta::new(*payer, true),
+                AccountMeta::new_readonly(system_program::id(), false),
+            ],
+            signers,
+        ),
+    )
+}
+
+/// Creates a `SetPauseFlags` instruction.
+pub fn set_pause_flags(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    exchange_booth: &Pubkey,
+    signers: &[&Pubkey],
+    pause_flags: u8,
+) -> Instruction {
+    build(
+        program_id,
+        ExchangeBoothInstruction::SetPauseFlags { pause_flags },
+        admin_metas(admin, vec![AccountMeta::new(*exchange_booth, false)], signers),
+    )
+}
+
+/// Creates a `QueueConfigChange` instruction.
+#[allow(clippy::too_many_arguments)]
+pub fn queue_config_change(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    exchange_booth: &Pubkey,
+    payer: &Pubkey,
+    signers: &[&Pubkey],
+    change_id: u64,
+    change: ConfigChange,
+    earliest_apply_slot: u64,
+) -> Instruction {
+    let (queued_change, _) = QueuedConfigChange::find_address(exchange_booth, change_id, program_id);
+    build(
+        program_id,
+        ExchangeBoothInstruction::QueueConfigChange { change_id, change, earliest_apply_slot },
+        admin_metas(
+            admin,
+            vec![
+                AccountMeta::new_readonly(*exchange_booth, false),
+                AccountMeta::new(queued_change, false),
+                AccountMeta::new(*payer, true),
+                AccountMeta::new_readonly(system_program::id(), false),
+            ],
+            signers,
+        ),
+    )
+}
+
+/// Creates an `ApplyConfigChange` instruction. `payer` is the account that paid for the
+/// queued change.
+pub fn apply_config_change(
+    program_id: &Pubkey,
+    exchange_booth: &Pubkey,
+    change_id: u64,
+    payer: &Pubkey,
+) -> Instruction {
+    let (queued_change, _) = QueuedConfigChange::find_address(exchange_booth, change_id, program_id);
+    build(
+        program_id,
+        ExchangeBoothInstruction::ApplyConfigChange {},
+        vec![
+            AccountMeta::new(*exchange_booth, false),
+            AccountMeta::new(queued_change, false),
+            AccountMeta::new(*payer, false),
+        ],
+    )
+}
+
+/// Creates a `CancelConfigChange` instruction.
+pub fn cancel_config_change(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    exchange_booth: &Pubkey,
+    change_id: u64,
+    payer: &Pubkey,
+    signers: &[&Pubkey],
+) -> Instruction {
+    let (queued_change, _) = QueuedConfigChange::find_address(exchange_booth, change_id, program_id);
+    build(
+        program_id,
+        ExchangeBoothInstruction::CancelConfigChange {},
+        admin_metas(
+            admin,
+            vec![
+                AccountMeta::new_readonly(*exchange_booth, false),
+                AccountMeta::new(queued_change, false),
+                AccountMeta::new(*payer, false),
+            ],
+            signers,
+        ),
+    )
+}
+
+/// Creates a `SetAllowlistEntry` instruction.
+pub fn set_allowlist_entry(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    exchange_booth: &Pubkey,
+    payer: &Pubkey,
+    signers: &[&Pubkey],
+    user: &Pubkey,
+    allowed: bool,
+) -> Instruction {
+    let (allowlist_entry, _) = AllowlistEntry::find_address(exchange_booth, user, program_id);
+    build(
+        program_id,
+        ExchangeBoothInstruction::SetAllowlistEntry { user: *user, allowed },
+        admin_metas(
+            admin,
+            vec![
+                AccountMeta::new_readonly(*exchange_booth, false),
+                AccountMeta::new(allowlist_entry, false),
+                AccountMeta::new(*payer, true),
+                AccountMeta::new_readonly(system_program::id(), false),
+            ],
+            signers,
+        ),
+    )
+}
+
+/// Creates a `QueueWithdrawal` instruction.
+#[allow(clippy::too_many_arguments)]
+pub fn queue_withdrawal(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    exchange_booth: &Pubkey,
+    vault: &Pubkey,
+    destination: &Pubkey,
+    payer: &Pubkey,
+    signers: &[&Pubkey],
+    withdrawal_id: u64,
+    amount: u64,
+) -> Instruction {
+    let (queued_withdrawal, _) = QueuedWithdrawal::find_address(exchange_booth, withdrawal_id, program_id);
+    build(
+        program_id,
+        ExchangeBoothInstruction::QueueWithdrawal { withdrawal_id, amount },
+        admin_metas(
+            admin,
+            vec![
+                AccountMeta::new_readonly(*exchange_booth, false),
+                AccountMeta::new(queued_withdrawal, false),
+                AccountMeta::new_readonly(*vault, false),
+                AccountMeta::new_readonly(*destination, false),
+                AccountMeta::new(*payer, true),
+                AccountMeta::new_readonly(system_program::id(), false),
+            ],
+            signers,
+        ),
+    )
+}
+
+/// Creates an `ExecuteWithdrawal` instruction. `vault`, `destination` and `payer` must be
+/// the ones recorded in the queued withdrawal, `mint` the vault's mint.
+#[allow(clippy::too_many_arguments)]
+pub fn execute_withdrawal(
+    program_id: &Pubkey,
+    exchange_booth: &Pubkey,
+    withdrawal_id: u64,
+    vault: &Pubkey,
+    mint: &Pubkey,
+    destination: &Pubkey,
+    payer: &Pubkey,
+    token_program: &Pubkey,
+) -> Instruction {
+    let (queued_withdrawal, _) = QueuedWithdrawal::find_address(exchange_booth, withdrawal_id, program_id);
+    build(
+        program_id,
+        ExchangeBoothInstruction::ExecuteWithdrawal {},
+        vec![
+            AccountMeta::new_readonly(*exchange_booth, false),
+            AccountMeta::new(queued_withdrawal, false),
+            AccountMeta::new(*vault, false),
+            AccountMeta::new_readonly(*mint, false),
+            AccountMeta::new(*destination, false),
+            AccountMeta::new(*payer, false),
+            AccountMeta::new_readonly(*token_program, false),
+        ],
+    )
+}
+
+/// Creates a `CancelWithdrawal` instruction.
+pub fn cancel_withdrawal(
+    program_id: &Pubkey,
+    admin: &Pubkey,
+    exchange_booth: &Pubkey,
+    withdrawal_id: u64,
+    payer: &Pubkey,
+    signers: &[&Pubkey],
+) -> Instruction {
+    let (queued_withdrawal, _) = QueuedWithdrawal::find_address(exchange_booth, withdrawal_id, program_id);
+    build(
+        program_id,
+        ExchangeBoothInstruction::CancelWithdrawal {},
+        admin_metas(
+            admin,
+            vec![
+                AccountMeta::new_readonly(*exchange_booth, false),
+                AccountMeta::new(queued_withdrawal, false),
+                AccountMeta::new(*payer, false),
+            ],
+            signers,
+        ),
+    )
+}
+
+/// Creates a `Migrate` instruction for the booth trading `mint_a` for `mint_b`.
+pub fn migrate(
+    program_id: &Pubkey,
+    payer: &Pubkey,
+    exchange_booth: &Pubkey,
+    mint_a: &Pubkey,
+    mint_b: &Pubkey,
+) -> Instruction {
+    let (registry, _) = BoothRegistry::find_address(mint_a, mint_b, program_id);
+    let (registry_index, _) = RegistryIndex::find_address(program_id);
+    build(
+        program_id,
+        ExchangeBoothInstruction::Migrate {},
+        vec![
+            AccountMeta::new(*payer, true),
+            AccountMeta::new(*exchange_booth, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new(registry, false),
+            AccountMeta::new(registry_index, false),
+        ],
+    )
 }
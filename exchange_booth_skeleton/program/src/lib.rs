@@ -1,5 +1,11 @@
+pub mod account;
+#[cfg(feature = "cpi")]
+pub mod cpi;
 pub mod entrypoint;
 pub mod error;
+pub mod events;
 pub mod instruction;
 pub mod processor;
 pub mod state;
+pub mod token;
+pub mod validation;
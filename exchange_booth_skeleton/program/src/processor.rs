@@ -10,10 +10,28 @@ use spl_token::{
 
 use crate::instruction::ExchangeBoothInstruction;
 
+pub mod accept_admin;
+pub mod apply_config_change;
+pub mod cancel_config_change;
+pub mod cancel_withdrawal;
 pub mod close_exchange_booth;
+pub mod collect_protocol_fees;
 pub mod deposit;
 pub mod exchange;
+pub mod execute_withdrawal;
+pub mod flash_borrow;
+pub mod flash_repay;
 pub mod initialize_exchange_booth;
+pub mod migrate;
+pub mod propose_admin;
+pub mod queue_config_change;
+pub mod queue_withdrawal;
+pub mod quote;
+pub mod route_exchange;
+pub mod set_admin_multisig;
+pub mod set_allowlist_entry;
+pub mod set_pause_flags;
+pub mod set_protocol_config;
 pub mod withdraw;
 
 pub struct Processor {}
@@ -28,26 +46,112 @@ impl Processor {
             .map_err(|_| ProgramError::InvalidInstructionData)?;
 
         match instruction {
-            ExchangeBoothInstruction::InititializeExchangeBooth { } => {
+            ExchangeBoothInstruction::InititializeExchangeBooth {
+                spread_bps,
+                flash_loan_fee_bps,
+                referral_share_bps,
+                withdrawal_limit,
+                withdrawal_window_slots,
+            } => {
                 msg!("Instruction: InitializeExchangeBooth");
-                initialize_exchange_booth::process(program_id, accounts)?;
+                initialize_exchange_booth::process(
+                    program_id,
+                    accounts,
+                    spread_bps,
+                    flash_loan_fee_bps,
+                    referral_share_bps,
+                    withdrawal_limit,
+                    withdrawal_window_slots,
+                )?;
             }
-            ExchangeBoothInstruction::Deposit { } => {
+            ExchangeBoothInstruction::Deposit { amount } => {
                 msg!("Instruction: Deposit");
-                deposit::process(program_id, accounts)?;
+                deposit::process(program_id, accounts, amount)?;
             }
             ExchangeBoothInstruction::Withdraw { amount_to_withdraw } => {
                 msg!("Instruction: Withdraw");
                 withdraw::process(program_id, accounts, amount_to_withdraw)?;
             }
-            ExchangeBoothInstruction::Exchange {tokens_to_transfer} => {
-                msg!("Instruction: Withdraw");
-                exchange::process(program_id, accounts, tokens_to_transfer)?;
+            ExchangeBoothInstruction::Exchange { tokens_to_transfer, min_amount_out } => {
+                msg!("Instruction: Exchange");
+                exchange::process(program_id, accounts, tokens_to_transfer, min_amount_out)?;
             }
             ExchangeBoothInstruction::CloseExchangeBooth { } => {
                 msg!("Instruction: CloseExchangeBooth");
                 close_exchange_booth::process(program_id, accounts)?;
             }
+            ExchangeBoothInstruction::RouteExchange { amount_in, min_amount_out } => {
+                msg!("Instruction: RouteExchange");
+                route_exchange::process(program_id, accounts, amount_in, min_amount_out)?;
+            }
+            ExchangeBoothInstruction::FlashBorrow { amount } => {
+                msg!("Instruction: FlashBorrow");
+                flash_borrow::process(program_id, accounts, amount)?;
+            }
+            ExchangeBoothInstruction::FlashRepay { amount } => {
+                msg!("Instruction: FlashRepay");
+                flash_repay::process(program_id, accounts, amount)?;
+            }
+            ExchangeBoothInstruction::SetProtocolConfig { treasury_owner, protocol_fee_share_bps } => {
+                msg!("Instruction: SetProtocolConfig");
+                set_protocol_config::process(program_id, accounts, treasury_owner, protocol_fee_share_bps)?;
+            }
+            ExchangeBoothInstruction::CollectProtocolFees { } => {
+                msg!("Instruction: CollectProtocolFees");
+                collect_protocol_fees::process(program_id, accounts)?;
+            }
+            ExchangeBoothInstruction::ProposeAdmin { new_admin } => {
+                msg!("Instruction: ProposeAdmin");
+                propose_admin::process(program_id, accounts, new_admin)?;
+            }
+            ExchangeBoothInstruction::AcceptAdmin { } => {
+                msg!("Instruction: AcceptAdmin");
+                accept_admin::process(program_id, accounts)?;
+            }
+            ExchangeBoothInstruction::SetAdminMultisig { signers, threshold } => {
+                msg!("Instruction: SetAdminMultisig");
+                set_admin_multisig::process(program_id, accounts, signers, threshold)?;
+            }
+            ExchangeBoothInstruction::SetPauseFlags { pause_flags } => {
+                msg!("Instruction: SetPauseFlags");
+                set_pause_flags::process(program_id, accounts, pause_flags)?;
+            }
+            ExchangeBoothInstruction::QueueConfigChange { change_id, change, earliest_apply_slot } => {
+                msg!("Instruction: QueueConfigChange");
+                queue_config_change::process(program_id, accounts, change_id, change, earliest_apply_slot)?;
+            }
+            ExchangeBoothInstruction::ApplyConfigChange { } => {
+                msg!("Instruction: ApplyConfigChange");
+                apply_config_change::process(program_id, accounts)?;
+            }
+            ExchangeBoothInstruction::CancelConfigChange { } => {
+                msg!("Instruction: CancelConfigChange");
+                cancel_config_change::process(program_id, accounts)?;
+            }
+            ExchangeBoothInstruction::SetAllowlistEntry { user, allowed } => {
+                msg!("Instruction: SetAllowlistEntry");
+                set_allowlist_entry::process(program_id, accounts, user, allowed)?;
+            }
+            ExchangeBoothInstruction::QueueWithdrawal { withdrawal_id, amount } => {
+                msg!("Instruction: QueueWithdrawal");
+                queue_withdrawal::process(program_id, accounts, withdrawal_id, amount)?;
+            }
+            ExchangeBoothInstruction::ExecuteWithdrawal { } => {
+                msg!("Instruction: ExecuteWithdrawal");
+                execute_withdrawal::process(program_id, accounts)?;
+            }
+            ExchangeBoothInstruction::CancelWithdrawal { } => {
+                msg!("Instruction: CancelWithdrawal");
+                cancel_withdrawal::process(program_id, accounts)?;
+            }
+            ExchangeBoothInstruction::Migrate { } => {
+                msg!("Instruction: Migrate");
+                migrate::process(program_id, accounts)?;
+            }
+            ExchangeBoothInstruction::Quote { tokens_to_transfer } => {
+                msg!("Instruction: Quote");
+                quote::process(program_id, accounts, tokens_to_transfer)?;
+            }
         }
 
         Ok(())
@@ -0,0 +1,50 @@
+use solana_program::{
+    account_info::{AccountInfo, next_account_info},
+    entrypoint::ProgramResult, msg,
+    pubkey::Pubkey,
+};
+
+use crate::{
+    error::ExchangeBoothError,
+    state::{AdminMultisig, ExchangeBooth},
+    validation::{assert_owned_by, assert_with_msg},
+};
+
+pub fn process(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+    let pending_admin = next_account_info(accounts_iter)?;
+    let exchange_booth_acct = next_account_info(accounts_iter)?;
+
+    assert_owned_by(
+        exchange_booth_acct,
+        program_id,
+        "'Exchange Booth' Account is not owned by the Exchange Booth program.",
+    )?;
+
+    let mut exchange_booth = ExchangeBooth::load_mut(exchange_booth_acct, program_id)?;
+
+    assert_with_msg(
+        exchange_booth.pending_admin != Pubkey::default(),
+        ExchangeBoothError::IncorrectAdmin.into(),
+        "No new Admin has been proposed.",
+    )?;
+
+    // the new admin has to prove it can sign, which rules out typos and unusable keys
+    AdminMultisig::authorize(
+        program_id,
+        exchange_booth_acct.key,
+        &exchange_booth.pending_admin,
+        pending_admin,
+        accounts_iter.as_slice(),
+    )?;
+
+    exchange_booth.admin = exchange_booth.pending_admin;
+    exchange_booth.pending_admin = Pubkey::default();
+
+    msg!("'{}' is now the Admin.", exchange_booth.admin);
+
+    Ok(())
+}
@@ -0,0 +1,79 @@
+use solana_program::{
+    account_info::{AccountInfo, next_account_info},
+    clock::Clock,
+    entrypoint::ProgramResult, msg,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvar::Sysvar,
+};
+
+use crate::{
+    error::ExchangeBoothError,
+    events::BoothEvent,
+    state::{ExchangeBooth, QueuedConfigChange},
+    validation::{assert_key, assert_owned_by, assert_with_msg},
+};
+
+pub fn process(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+    let exchange_booth_acct = next_account_info(accounts_iter)?;
+    let queued_change_acct = next_account_info(accounts_iter)?;
+    let payer = next_account_info(accounts_iter)?;
+
+    assert_owned_by(
+        exchange_booth_acct,
+        program_id,
+        "Exchange Booth and queued change must be owned by the Exchange Booth program.",
+    )?;
+    assert_owned_by(
+        queued_change_acct,
+        program_id,
+        "Exchange Booth and queued change must be owned by the Exchange Booth program.",
+    )?;
+
+    let mut exchange_booth = ExchangeBooth::load_mut(exchange_booth_acct, program_id)?;
+    let queued_change = QueuedConfigChange::load(queued_change_acct)?;
+
+    assert_with_msg(
+        queued_change.exchange_booth == *exchange_booth_acct.key,
+        ExchangeBoothError::InvalidAccountData.into(),
+        "Queued change belongs to a different Exchange Booth.",
+    )?;
+
+    assert_key(
+        payer,
+        &queued_change.payer,
+        "Third account passed for 'Payer' did not pay for the queued change.",
+    )?;
+
+    // anyone can apply the change once the timelock is over
+    assert_with_msg(
+        Clock::get()?.slot >= queued_change.earliest_apply_slot,
+        ExchangeBoothError::ConfigChangeTimelocked.into(),
+        "Queued change can not be applied yet.",
+    )?;
+
+    queued_change.change.apply(&mut exchange_booth)?;
+
+    // close the queued change and refund its rent
+    let change_lamports = queued_change_acct.lamports();
+    **queued_change_acct.lamports.borrow_mut() = 0;
+    **payer.lamports.borrow_mut() = payer
+        .lamports()
+        .checked_add(change_lamports)
+        .ok_or(ProgramError::InvalidArgument)?;
+    queued_change_acct.data.borrow_mut().fill(0);
+
+    msg!("Applied {:?}.", queued_change.change);
+
+    BoothEvent::ConfigChanged {
+        exchange_booth: *exchange_booth_acct.key,
+        change: queued_change.change,
+    }
+    .emit();
+
+    Ok(())
+}
@@ -0,0 +1,71 @@
+use solana_program::{
+    account_info::{AccountInfo, next_account_info},
+    entrypoint::ProgramResult, msg,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+use crate::{
+    error::ExchangeBoothError,
+    state::{AdminMultisig, ExchangeBooth, QueuedConfigChange},
+    validation::{assert_key, assert_owned_by, assert_with_msg},
+};
+
+pub fn process(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+    let admin = next_account_info(accounts_iter)?;
+    let exchange_booth_acct = next_account_info(accounts_iter)?;
+    let queued_change_acct = next_account_info(accounts_iter)?;
+    let payer = next_account_info(accounts_iter)?;
+
+    assert_owned_by(
+        exchange_booth_acct,
+        program_id,
+        "Exchange Booth and queued change must be owned by the Exchange Booth program.",
+    )?;
+    assert_owned_by(
+        queued_change_acct,
+        program_id,
+        "Exchange Booth and queued change must be owned by the Exchange Booth program.",
+    )?;
+
+    let exchange_booth = ExchangeBooth::load(exchange_booth_acct, program_id)?;
+
+    AdminMultisig::authorize(
+        program_id,
+        exchange_booth_acct.key,
+        &exchange_booth.admin,
+        admin,
+        accounts_iter.as_slice(),
+    )?;
+
+    let queued_change = QueuedConfigChange::load(queued_change_acct)?;
+
+    assert_with_msg(
+        queued_change.exchange_booth == *exchange_booth_acct.key,
+        ExchangeBoothError::InvalidAccountData.into(),
+        "Queued change belongs to a different Exchange Booth.",
+    )?;
+
+    assert_key(
+        payer,
+        &queued_change.payer,
+        "Fourth account passed for 'Payer' did not pay for the queued change.",
+    )?;
+
+    // close the queued change and refund its rent
+    let change_lamports = queued_change_acct.lamports();
+    **queued_change_acct.lamports.borrow_mut() = 0;
+    **payer.lamports.borrow_mut() = payer
+        .lamports()
+        .checked_add(change_lamports)
+        .ok_or(ProgramError::InvalidArgument)?;
+    queued_change_acct.data.borrow_mut().fill(0);
+
+    msg!("Cancelled {:?}.", queued_change.change);
+
+    Ok(())
+}
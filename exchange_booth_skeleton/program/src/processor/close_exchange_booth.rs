@@ -1,21 +1,124 @@
 use solana_program::{
     account_info::{AccountInfo, next_account_info},
-    entrypoint::ProgramResult, msg, 
+    entrypoint::ProgramResult, msg,
+    program::invoke_signed,
+    program_error::ProgramError,
     pubkey::Pubkey,
 };
 
 use crate::{
-    error::ExchangeBoothError,
-    state::ExchangeBooth,
+    account::shrink,
+    events::BoothEvent,
+    state::{AdminMultisig, BoothRegistry, ExchangeBooth},
+    token::{assert_token_program, unpack_token_account},
+    validation::{assert_key, assert_owned_by, assert_pda, assert_unique, assert_with_msg, assert_writable},
 };
 
-use borsh::{BorshDeserialize, BorshSerialize};
-
-
 pub fn process(
     program_id: &Pubkey,
     accounts: &[AccountInfo],
-    // ???
 ) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+    let admin = next_account_info(accounts_iter)?;
+    let exchange_booth_acct = next_account_info(accounts_iter)?;
+    let vault_a = next_account_info(accounts_iter)?;
+    let vault_b = next_account_info(accounts_iter)?;
+    let registry = next_account_info(accounts_iter)?;
+    let token_program = next_account_info(accounts_iter)?;
+    let rent_receiver = next_account_info(accounts_iter)?;
+
+    assert_owned_by(
+        exchange_booth_acct,
+        program_id,
+        "'Exchange Booth' Account is not owned by the Exchange Booth program.",
+    )?;
+
+    let exchange_booth = ExchangeBooth::load(exchange_booth_acct, program_id)?;
+
+    AdminMultisig::authorize(
+        program_id,
+        exchange_booth_acct.key,
+        &exchange_booth.admin,
+        admin,
+        accounts_iter.as_slice(),
+    )?;
+
+    assert_key(
+        vault_a,
+        &exchange_booth.vault_a,
+        "Vaults passed in do not match the Vaults of the Exchange Booth.",
+    )?;
+    assert_key(
+        vault_b,
+        &exchange_booth.vault_b,
+        "Vaults passed in do not match the Vaults of the Exchange Booth.",
+    )?;
+
+    assert_token_program(token_program, &[vault_a, vault_b])?;
+
+    // the admin may be a multisig PDA that can't spend lamports, so the signers name who
+    // gets the rent
+    assert_writable(rent_receiver, "The rent receiver must be writable")?;
+    assert_unique(
+        &[rent_receiver, exchange_booth_acct, vault_a, vault_b, registry],
+        "The rent receiver can't be one of the accounts being closed",
+    )?;
+
+    assert_pda(
+        registry,
+        BoothRegistry::find_address(&exchange_booth.mint_a, &exchange_booth.mint_b, program_id),
+        "Key returned from find_program_address was not equal to the key passed as the 'registry' Account.",
+    )?;
+
+    // vaults must be drained with Withdraw before the booth can be closed
+    for (vault, seed) in [(vault_a, b"vault_a"), (vault_b, b"vault_b")] {
+        let vault_state = unpack_token_account(vault)?;
+        assert_with_msg(
+            vault_state.amount == 0,
+            ProgramError::InvalidAccountData,
+            "Vaults must be empty before closing the Exchange Booth.",
+        )?;
+
+        let (_, bump) = Pubkey::find_program_address(
+            &[seed, exchange_booth_acct.key.as_ref()], program_id);
+
+        let close_ix = spl_token_2022::instruction::close_account(
+            token_program.key,
+            vault.key,
+            rent_receiver.key,
+            vault.key,
+            &[])?;
+
+        invoke_signed(&close_ix,
+            &[vault.clone(), rent_receiver.clone(), token_program.clone()],
+            &[&[seed, exchange_booth_acct.key.as_ref(), &[bump]]])?;
+    }
+
+    msg!("Vaults closed. Removing the Exchange Booth from the registry.");
+
+    // the registry gives up the booth's key and the rent for it
+    let mut booth_registry = BoothRegistry::load(registry)?;
+    booth_registry.remove(exchange_booth_acct.key)?;
+    booth_registry.save(registry)?;
+    shrink(registry, rent_receiver, BoothRegistry::len(booth_registry.booths.len()))?;
+
+    // hand the rent to the receiver and wipe the booth
+    drop(exchange_booth);
+    let booth_lamports = exchange_booth_acct.lamports();
+    **exchange_booth_acct.lamports.borrow_mut() = 0;
+    **rent_receiver.lamports.borrow_mut() = rent_receiver
+        .lamports()
+        .checked_add(booth_lamports)
+        .ok_or(ProgramError::InvalidArgument)?;
+    exchange_booth_acct.data.borrow_mut().fill(0);
+
+    msg!("Exchange Booth closed.");
+
+    BoothEvent::Closed {
+        exchange_booth: *exchange_booth_acct.key,
+        admin: *admin.key,
+    }
+    .emit();
+
     Ok(())
 }
@@ -0,0 +1,110 @@
+use solana_program::{
+    account_info::{AccountInfo, next_account_info},
+    entrypoint::ProgramResult, msg,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+use crate::{
+    error::ExchangeBoothError,
+    state::{ExchangeBooth, ProtocolConfig},
+    token::{self, assert_token_program},
+    validation::{assert_key, assert_owned_by, assert_signer, assert_with_msg},
+};
+
+/// Accounts passed per booth: exchange_booth, vault_a, vault_b, mint_a, mint_b, treasury_token_a,
+/// treasury_token_b.
+const ACCOUNTS_PER_BOOTH: usize = 7;
+
+pub fn process(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+    let treasury_owner = next_account_info(accounts_iter)?;
+    let protocol_config_acct = next_account_info(accounts_iter)?;
+    let token_program = next_account_info(accounts_iter)?;
+
+    assert_signer(
+        treasury_owner,
+        "First account passed for 'Treasury Owner' is not a signer as is required.",
+    )?;
+
+    let protocol_config = ProtocolConfig::load_or_default(protocol_config_acct, program_id)?;
+    assert_with_msg(
+        !protocol_config_acct.data_is_empty() && protocol_config.treasury_owner == *treasury_owner.key,
+        ExchangeBoothError::IncorrectAdmin.into(),
+        "First account passed for 'Treasury Owner' is not the treasury owner.",
+    )?;
+
+    // `is_multiple_of` is newer than the rustc of the SBF toolchain
+    #[allow(unknown_lints, clippy::manual_is_multiple_of)]
+    assert_with_msg(
+        accounts_iter.len() % ACCOUNTS_PER_BOOTH == 0,
+        ProgramError::NotEnoughAccountKeys,
+        "Booth accounts must come in groups of seven.",
+    )?;
+
+    while accounts_iter.len() > 0 {
+        let exchange_booth_acct = next_account_info(accounts_iter)?;
+        let vault_a = next_account_info(accounts_iter)?;
+        let vault_b = next_account_info(accounts_iter)?;
+        let mint_a = next_account_info(accounts_iter)?;
+        let mint_b = next_account_info(accounts_iter)?;
+        let treasury_token_a = next_account_info(accounts_iter)?;
+        let treasury_token_b = next_account_info(accounts_iter)?;
+
+        assert_owned_by(
+            exchange_booth_acct,
+            program_id,
+            "'Exchange Booth' Account is not owned by the Exchange Booth program.",
+        )?;
+
+        let mut exchange_booth = ExchangeBooth::load_mut(exchange_booth_acct, program_id)?;
+
+        assert_key(
+            vault_a,
+            &exchange_booth.vault_a,
+            "Vaults passed in do not match the Vaults of the Exchange Booth.",
+        )?;
+        assert_key(
+            vault_b,
+            &exchange_booth.vault_b,
+            "Vaults passed in do not match the Vaults of the Exchange Booth.",
+        )?;
+        assert_key(
+            mint_a,
+            &exchange_booth.mint_a,
+            "Mints passed in do not match the mints of the Exchange Booth.",
+        )?;
+        assert_key(
+            mint_b,
+            &exchange_booth.mint_b,
+            "Mints passed in do not match the mints of the Exchange Booth.",
+        )?;
+        assert_token_program(token_program, &[mint_a, mint_b])?;
+
+        for (vault, mint, seed, treasury_token, amount) in [
+            (vault_a, mint_a, b"vault_a", treasury_token_a, exchange_booth.protocol_fees_a),
+            (vault_b, mint_b, b"vault_b", treasury_token_b, exchange_booth.protocol_fees_b),
+        ] {
+            if amount == 0 {
+                continue;
+            }
+
+            let (_, bump) = Pubkey::find_program_address(
+                &[seed, exchange_booth_acct.key.as_ref()], program_id);
+
+            token::transfer_checked(token_program, vault, mint, treasury_token, vault, amount,
+                &[&[seed, exchange_booth_acct.key.as_ref(), &[bump]]])?;
+        }
+
+        msg!("Collected {} A and {} B from booth {}.",
+            { exchange_booth.protocol_fees_a }, { exchange_booth.protocol_fees_b }, exchange_booth_acct.key);
+
+        exchange_booth.protocol_fees_a = 0;
+        exchange_booth.protocol_fees_b = 0;
+    }
+
+    Ok(())
+}
@@ -1,21 +1,93 @@
 use solana_program::{
     account_info::{AccountInfo, next_account_info},
-    entrypoint::ProgramResult, msg, 
+    entrypoint::ProgramResult, msg,
+    program_error::ProgramError,
     pubkey::Pubkey,
 };
 
 use crate::{
     error::ExchangeBoothError,
-    state::ExchangeBooth,
+    events::BoothEvent,
+    state::{ExchangeBooth, PAUSE_DEPOSITS},
+    token::{self, assert_token_program},
+    validation::{assert_owned_by, assert_signer, assert_unique, assert_with_msg},
 };
 
-use borsh::{BorshDeserialize, BorshSerialize};
-
-
 pub fn process(
     program_id: &Pubkey,
     accounts: &[AccountInfo],
-    // ???
+    amount: u64,
 ) -> ProgramResult {
+    assert_with_msg(amount > 0, ProgramError::InvalidArgument, "The number of tokens to deposit must be > 0")?;
+
+    let accounts_iter = &mut accounts.iter();
+    let depositor = next_account_info(accounts_iter)?;
+    let depositor_token_account = next_account_info(accounts_iter)?;
+    let vault = next_account_info(accounts_iter)?;
+    let mint = next_account_info(accounts_iter)?;
+    let exchange_booth_acct = next_account_info(accounts_iter)?;
+    let token_program = next_account_info(accounts_iter)?;
+
+    assert_unique(
+        &[depositor_token_account, vault],
+        "The depositor token account can not be the Vault.",
+    )?;
+
+    assert_signer(
+        depositor,
+        "First account passed for 'Depositor' is not a signer as is required.",
+    )?;
+
+    assert_owned_by(
+        exchange_booth_acct,
+        program_id,
+        "'Exchange Booth' Account is not owned by the Exchange Booth program.",
+    )?;
+
+    let exchange_booth = ExchangeBooth::load(exchange_booth_acct, program_id)?;
+    exchange_booth.check_not_paused(PAUSE_DEPOSITS)?;
+
+    assert_with_msg(
+        (*vault.key == exchange_booth.vault_a && *mint.key == exchange_booth.mint_a)
+            || (*vault.key == exchange_booth.vault_b && *mint.key == exchange_booth.mint_b),
+        ExchangeBoothError::InvalidAccountAddress.into(),
+        "Vault and mint passed in are not a Vault of the Exchange Booth and its mint.",
+    )?;
+    assert_token_program(token_program, &[mint])?;
+
+    // Lamports go in through the depositor's wrapped SOL PDA, opened and closed right here
+    let (wsol, _) = token::find_wrapped_sol_address(exchange_booth_acct.key, depositor.key, program_id);
+    let wrap = *depositor_token_account.key == wsol;
+    if wrap {
+        // Only needed to open the wrapped SOL PDA, so token deposits can leave it out
+        let system_program = next_account_info(accounts_iter)?;
+        token::wrap_sol(
+            program_id,
+            exchange_booth_acct.key,
+            depositor,
+            depositor_token_account,
+            mint,
+            token_program,
+            system_program,
+            amount,
+        )?;
+    }
+
+    token::transfer_checked(token_program, depositor_token_account, mint, vault, depositor, amount, &[])?;
+
+    if wrap {
+        token::unwrap_sol(token_program, depositor_token_account, depositor)?;
+    }
+
+    msg!("Deposited {} tokens.", amount);
+
+    BoothEvent::Deposited {
+        exchange_booth: *exchange_booth_acct.key,
+        depositor: *depositor.key,
+        vault: *vault.key,
+        amount,
+    }
+    .emit();
+
     Ok(())
 }
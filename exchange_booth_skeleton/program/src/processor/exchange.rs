@@ -1,35 +1,442 @@
+use borsh::BorshSerialize;
 use solana_program::{
     account_info::{AccountInfo, next_account_info},
-    entrypoint::ProgramResult, msg, 
+    clock::Clock,
+    entrypoint::ProgramResult, msg,
+    program::set_return_data,
     pubkey::Pubkey,
-    program_error::ProgramError
+    program_error::ProgramError,
+    sysvar::{rent::Rent, Sysvar},
+};
+use std::{
+    cell::{Ref, RefMut},
+    ops::Deref,
 };
 
 use crate::{
+    account::create_pda_account,
     error::ExchangeBoothError,
-    state::ExchangeBooth,
+    events::BoothEvent,
+    instruction::ExchangeResult,
+    state::{AllowlistEntry, ExchangeBooth, OraclePrice, ProtocolConfig, UserVolume, PAUSE_EXCHANGES},
+    token::{self, assert_token_program, unpack_mint, unpack_token_account},
+    validation::{assert_key, assert_pda, assert_signer, assert_unique, assert_with_msg, assert_writable},
 };
 
-use spl_token::{
-    solana_program::program_pack::Pack,
-    state::{Account as TokenAccount, Mint},
-};
+/// The accounts describing a single booth, in the order they are passed to the program.
+pub struct BoothAccounts<'a, 'info> {
+    pub exchange_booth: &'a AccountInfo<'info>,
+    pub vault_a: &'a AccountInfo<'info>,
+    pub vault_b: &'a AccountInfo<'info>,
+    pub mint_a: &'a AccountInfo<'info>,
+    pub mint_b: &'a AccountInfo<'info>,
+    pub oracle: &'a AccountInfo<'info>,
+}
+
+impl<'a, 'info> BoothAccounts<'a, 'info> {
+    pub const LEN: usize = 6;
+
+    pub fn next<I: Iterator<Item = &'a AccountInfo<'info>>>(accounts_iter: &mut I) -> Result<Self, ProgramError> {
+        Ok(Self {
+            exchange_booth: next_account_info(accounts_iter)?,
+            vault_a: next_account_info(accounts_iter)?,
+            vault_b: next_account_info(accounts_iter)?,
+            mint_a: next_account_info(accounts_iter)?,
+            mint_b: next_account_info(accounts_iter)?,
+            oracle: next_account_info(accounts_iter)?,
+        })
+    }
+
+    /// The mint of the booth's native SOL side.
+    pub fn native_mint(&self) -> Result<&'a AccountInfo<'info>, ProgramError> {
+        [self.mint_a, self.mint_b]
+            .into_iter()
+            .find(|mint| token::is_native_mint(mint.key))
+            .ok_or_else(|| {
+                msg!("Lamports can only be paid into the native SOL side of a booth");
+                ExchangeBoothError::NoNativeSide.into()
+            })
+    }
+
+    /// Mint of one of the user's token accounts. The user's wrapped SOL account only exists
+    /// while an exchange runs, so before that it stands for the booth's native mint.
+    pub fn user_account_mint(&self, program_id: &Pubkey, user: &Pubkey, account: &AccountInfo) -> Result<Pubkey, ProgramError> {
+        let (wsol, _) = token::find_wrapped_sol_address(self.exchange_booth.key, user, program_id);
+        if *account.key == wsol && account.data_is_empty() {
+            return Ok(*self.native_mint()?.key);
+        }
+        Ok(unpack_token_account(account)?.mint)
+    }
+}
+
+/// The priced result of pushing `amount_in` through one booth.
+///
+/// With Token-2022 transfer fees the input vault receives less than `amount_in` and the
+/// destination less than `payout`; the leg is priced on what the vault receives.
+///
+/// `quote` only reads the booth; `into_mut` trades that borrow for a writable one before
+/// volume and fees are recorded.
+pub struct SwapLeg<'a, 'info, B = RefMut<'a, ExchangeBooth>> {
+    pub a_to_b: bool,
+    pub amount_in: u64,
+    pub amount_out: u64, // arrives at the destination
+    pub payout: u64,     // leaves the output vault: amount_out plus the output mint's transfer fee
+    pub fee: u64,
+    pub referral_fee: u64, // part of `fee` paid to a referrer instead of the booth
+    pub price: OraclePrice,
+    pub exchange_booth: B, // borrowed from the booth account's data
+    pub input_vault: &'a AccountInfo<'info>,
+    pub output_vault: &'a AccountInfo<'info>,
+}
+
+impl<'a, 'info, B: Deref<Target = ExchangeBooth>> SwapLeg<'a, 'info, B> {
+    pub fn output_mint(&self) -> Pubkey {
+        if self.a_to_b { self.exchange_booth.mint_b } else { self.exchange_booth.mint_a }
+    }
+
+    pub fn input_mint_account<'b>(&self, booth: &BoothAccounts<'b, 'info>) -> &'b AccountInfo<'info> {
+        if self.a_to_b { booth.mint_a } else { booth.mint_b }
+    }
+
+    pub fn output_mint_account<'b>(&self, booth: &BoothAccounts<'b, 'info>) -> &'b AccountInfo<'info> {
+        if self.a_to_b { booth.mint_b } else { booth.mint_a }
+    }
+
+    /// Traded volume in token A units: the input for A to B, the gross output for B to A.
+    pub fn volume_a(&self) -> u64 {
+        if self.a_to_b { self.amount_in } else { self.payout.saturating_add(self.fee) }
+    }
 
-use borsh::{BorshDeserialize, BorshSerialize};
+    pub fn event(&self, exchange_booth: &Pubkey, user: &Pubkey, referrer: Option<Pubkey>) -> BoothEvent {
+        let input_mint = if self.a_to_b { self.exchange_booth.mint_a } else { self.exchange_booth.mint_b };
+        BoothEvent::Exchanged {
+            exchange_booth: *exchange_booth,
+            user: *user,
+            input_mint,
+            output_mint: self.output_mint(),
+            amount_in: self.amount_in,
+            amount_out: self.amount_out,
+            fee: self.fee,
+            referrer,
+            referral_fee: self.referral_fee,
+        }
+    }
 
-pub fn assert_with_msg(statement: bool, err: ProgramError, msg: &str) -> ProgramResult {
-    if !statement {
-        msg!(msg);
-        Err(err)
+    /// Seed prefix of the output vault, which is its own token authority.
+    pub fn output_vault_seed(&self) -> &'static [u8] {
+        if self.a_to_b { b"vault_b" } else { b"vault_a" }
+    }
+}
+
+impl<'a, 'info> SwapLeg<'a, 'info, Ref<'a, ExchangeBooth>> {
+    /// Drops the read-only borrow of the booth and borrows it writable instead.
+    pub fn into_mut(self, program_id: &Pubkey, booth: &BoothAccounts<'a, 'info>) -> Result<SwapLeg<'a, 'info>, ProgramError> {
+        let SwapLeg { a_to_b, amount_in, amount_out, payout, fee, referral_fee, price, exchange_booth, input_vault, output_vault } =
+            self;
+        drop(exchange_booth);
+        Ok(SwapLeg {
+            a_to_b,
+            amount_in,
+            amount_out,
+            payout,
+            fee,
+            referral_fee,
+            price,
+            exchange_booth: ExchangeBooth::load_mut(booth.exchange_booth, program_id)?,
+            input_vault,
+            output_vault,
+        })
+    }
+}
+
+/// Converts `amount_in` raw units of the input token to raw units of the output token at the
+/// oracle price, then takes the booth spread out of the result.
+///
+/// Returns `(amount_out, fee)`, both in the output token.
+pub fn calculate_amount_out(
+    amount_in: u64,
+    a_to_b: bool,
+    oracle_price: &OraclePrice,
+    decimals_a: u8,
+    decimals_b: u8,
+    spread_bps: u64,
+) -> Result<(u64, u64), ProgramError> {
+    let pow10 = |exp: u8| 10u128.checked_pow(exp as u32).ok_or(ProgramError::InvalidArgument);
+    let mul = |x: u128, y: u128| x.checked_mul(y).ok_or(ProgramError::InvalidArgument);
+
+    let price = oracle_price.price as u128;
+    let (numerator, denominator) = if a_to_b {
+        (mul(price, pow10(decimals_b)?)?, mul(pow10(oracle_price.decimals)?, pow10(decimals_a)?)?)
     } else {
-        Ok(())
+        (mul(pow10(oracle_price.decimals)?, pow10(decimals_a)?)?, mul(price, pow10(decimals_b)?)?)
+    };
+
+    let gross_out = mul(amount_in as u128, numerator)? / denominator;
+    let fee = gross_out * spread_bps.min(10_000) as u128 / 10_000;
+
+    let to_u64 = |x: u128| u64::try_from(x).map_err(|_| ProgramError::from(ExchangeBoothError::AmountOverflow));
+    Ok((to_u64(gross_out - fee)?, to_u64(fee)?))
+}
+
+/// Validates one booth's accounts against its state and prices `amount_in` of `input_mint`.
+/// Moves no funds.
+pub fn quote<'a, 'info>(
+    program_id: &Pubkey,
+    booth: &BoothAccounts<'a, 'info>,
+    input_mint: &Pubkey,
+    amount_in: u64,
+) -> Result<SwapLeg<'a, 'info, Ref<'a, ExchangeBooth>>, ProgramError> {
+    let exchange_booth = ExchangeBooth::load(booth.exchange_booth, program_id)?;
+    exchange_booth.check_not_paused(PAUSE_EXCHANGES)?;
+
+    // Validate the vault, mint and oracle accounts against the ones stored in the exchange booth
+    assert_key(
+        booth.vault_a,
+        &exchange_booth.vault_a,
+        "Vault accounts do not match the Exchange Booth",
+    )?;
+    assert_key(
+        booth.vault_b,
+        &exchange_booth.vault_b,
+        "Vault accounts do not match the Exchange Booth",
+    )?;
+    assert_key(
+        booth.mint_a,
+        &exchange_booth.mint_a,
+        "Mint accounts do not match the Exchange Booth",
+    )?;
+    assert_key(
+        booth.mint_b,
+        &exchange_booth.mint_b,
+        "Mint accounts do not match the Exchange Booth",
+    )?;
+    assert_key(
+        booth.oracle,
+        &exchange_booth.oracle,
+        "Oracle account does not match the Exchange Booth",
+    )?;
+
+    // The input token picks the direction
+    let a_to_b = if *input_mint == exchange_booth.mint_a {
+        true
+    } else if *input_mint == exchange_booth.mint_b {
+        false
+    } else {
+        msg!("Input token is not traded by this Exchange Booth");
+        return Err(ExchangeBoothError::InvalidAccountData.into());
+    };
+
+    let mint_a = unpack_mint(booth.mint_a)?;
+    let mint_b = unpack_mint(booth.mint_b)?;
+    let oracle_price = OraclePrice::load(booth.oracle)?;
+
+    // Token-2022 transfer fees come out of both transfers, so price what the vault receives
+    let (input_mint_acct, output_mint_acct) = if a_to_b {
+        (booth.mint_a, booth.mint_b)
+    } else {
+        (booth.mint_b, booth.mint_a)
+    };
+    let amount_received = amount_in - token::transfer_fee(input_mint_acct, amount_in)?;
+
+    let (payout, fee) = calculate_amount_out(
+        amount_received,
+        a_to_b,
+        &oracle_price,
+        mint_a.decimals,
+        mint_b.decimals,
+        exchange_booth.spread_bps,
+    )?;
+    let amount_out = payout - token::transfer_fee(output_mint_acct, payout)?;
+
+    let (input_vault, output_vault) = if a_to_b {
+        (booth.vault_a, booth.vault_b)
+    } else {
+        (booth.vault_b, booth.vault_a)
+    };
+
+    // Fail early with a clear message rather than inside the token program
+    let output_vault_acc = unpack_token_account(output_vault)?;
+    assert_with_msg(
+        output_vault_acc.amount >= payout,
+        ProgramError::InsufficientFunds,
+        "The Exchange Booth vault does not hold enough tokens for this exchange",
+    )?;
+
+    msg!("Quoted {} in for {} out (fee {})", amount_in, amount_out, fee);
+
+    Ok(SwapLeg {
+        a_to_b,
+        amount_in,
+        amount_out,
+        payout,
+        fee,
+        referral_fee: 0,
+        price: oracle_price,
+        exchange_booth,
+        input_vault,
+        output_vault,
+    })
+}
+
+/// On an access gated booth, checks that `access_acct` lets `user` trade: either it is the
+/// user's allowlist entry, or a token account of the user holding at least
+/// `gating_min_balance` of the booth's gating mint.
+pub fn check_access<B: Deref<Target = ExchangeBooth>>(
+    program_id: &Pubkey,
+    booth: &BoothAccounts,
+    leg: &SwapLeg<'_, '_, B>,
+    user: &AccountInfo,
+    access_acct: &AccountInfo,
+) -> ProgramResult {
+    let exchange_booth = &leg.exchange_booth;
+    if exchange_booth.access_gated == 0 {
+        return Ok(());
+    }
+
+    let (allowlist_key, _) = AllowlistEntry::find_address(booth.exchange_booth.key, user.key, program_id);
+    if *access_acct.key == allowlist_key {
+        let allowed = access_acct.owner == program_id
+            && AllowlistEntry::load(access_acct)
+                .map(|entry| entry.exchange_booth == *booth.exchange_booth.key && entry.user == *user.key)
+                .unwrap_or(false);
+        return assert_with_msg(
+            allowed,
+            ExchangeBoothError::AccessDenied.into(),
+            "User is not on the booth's allowlist",
+        );
+    }
+
+    let holds_gating_tokens = exchange_booth.gating_mint != Pubkey::default()
+        && token::is_token_program(access_acct.owner)
+        && unpack_token_account(access_acct)
+            .map(|token| {
+                token.owner == *user.key
+                    && token.mint == exchange_booth.gating_mint
+                    && token.amount >= exchange_booth.gating_min_balance
+            })
+            .unwrap_or(false);
+    assert_with_msg(
+        holds_gating_tokens,
+        ExchangeBoothError::AccessDenied.into(),
+        "User has neither an allowlist entry nor enough of the gating token",
+    )
+}
+
+/// Adds the leg's volume to the booth's epoch volume and the user's volume PDA, failing if
+/// either cap is exceeded. Counters restart whenever the `Clock` epoch changes.
+pub fn record_volume<'info>(
+    program_id: &Pubkey,
+    booth: &BoothAccounts<'_, 'info>,
+    leg: &mut SwapLeg<'_, 'info>,
+    user: &AccountInfo<'info>,
+    user_volume_acct: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+) -> ProgramResult {
+    let epoch = Clock::get()?.epoch;
+    let volume = leg.volume_a();
+    let exchange_booth = &mut leg.exchange_booth;
+
+    if exchange_booth.epoch != epoch {
+        exchange_booth.epoch = epoch;
+        exchange_booth.epoch_volume = 0;
     }
+    exchange_booth.epoch_volume = exchange_booth.epoch_volume.saturating_add(volume);
+    assert_with_msg(
+        exchange_booth.epoch_cap == 0 || exchange_booth.epoch_volume <= exchange_booth.epoch_cap,
+        ExchangeBoothError::VolumeLimitExceeded.into(),
+        "Exchange would exceed the booth's volume cap for this epoch",
+    )?;
+
+    let bump = assert_pda(
+        user_volume_acct,
+        UserVolume::find_address(booth.exchange_booth.key, user.key, program_id),
+        "User volume account is not the PDA of the booth and user",
+    )?;
+
+    // without a per-user cap there is nothing to track, so don't make the user pay rent
+    if exchange_booth.per_user_epoch_cap == 0 {
+        return Ok(());
+    }
+
+    let mut user_volume = if user_volume_acct.data_is_empty() {
+        create_pda_account(
+            user,
+            user_volume_acct,
+            Rent::get()?.minimum_balance(UserVolume::LEN),
+            UserVolume::LEN,
+            program_id,
+            system_program,
+            &[b"user_volume", booth.exchange_booth.key.as_ref(), user.key.as_ref(), &[bump]],
+        )?;
+        UserVolume::default()
+    } else {
+        UserVolume::load(user_volume_acct)?
+    };
+    if user_volume.epoch != epoch {
+        user_volume.epoch = epoch;
+        user_volume.volume = 0;
+    }
+    user_volume.volume = user_volume.volume.saturating_add(volume);
+    assert_with_msg(
+        user_volume.volume <= exchange_booth.per_user_epoch_cap,
+        ExchangeBoothError::VolumeLimitExceeded.into(),
+        "Exchange would exceed the per-user volume cap for this epoch",
+    )?;
+    user_volume.save(user_volume_acct)
+}
+
+/// Books the leg's fee, less any referral, against the booth, splitting off the protocol's
+/// share. The fee is already sitting in the output vault, so this only updates the booth's
+/// balances.
+pub fn record_fee(leg: &mut SwapLeg, protocol_config: &ProtocolConfig) -> ProgramResult {
+    let (booth_share, protocol_share) = protocol_config.split_fee(leg.fee - leg.referral_fee);
+    let exchange_booth = &mut leg.exchange_booth;
+    let overflow = || ProgramError::InvalidArgument;
+    if leg.a_to_b {
+        exchange_booth.fees_b = exchange_booth.fees_b.checked_add(booth_share).ok_or_else(overflow)?;
+        exchange_booth.protocol_fees_b =
+            exchange_booth.protocol_fees_b.checked_add(protocol_share).ok_or_else(overflow)?;
+    } else {
+        exchange_booth.fees_a = exchange_booth.fees_a.checked_add(booth_share).ok_or_else(overflow)?;
+        exchange_booth.protocol_fees_a =
+            exchange_booth.protocol_fees_a.checked_add(protocol_share).ok_or_else(overflow)?;
+    }
+    Ok(())
+}
+
+/// Pays `amount` out of the leg's output vault, signing for the vault PDA. The destination
+/// gets `amount` less any transfer fee of the output mint.
+pub fn pay_out<'info>(
+    program_id: &Pubkey,
+    booth: &BoothAccounts<'_, 'info>,
+    leg: &SwapLeg<'_, 'info>,
+    destination: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+    amount: u64,
+) -> ProgramResult {
+    let seed = leg.output_vault_seed();
+    let bump = assert_pda(
+        leg.output_vault,
+        Pubkey::find_program_address(&[seed, booth.exchange_booth.key.as_ref()], program_id),
+        "Vault key from Find PDA doesn't match passed in Vault addr.",
+    )?;
+
+    token::transfer_checked(
+        token_program,
+        leg.output_vault,
+        leg.output_mint_account(booth),
+        destination,
+        leg.output_vault,
+        amount,
+        &[&[seed, booth.exchange_booth.key.as_ref(), &[bump]]],
+    )
 }
 
 pub fn process(
     program_id: &Pubkey,
     accounts: &[AccountInfo],
     tokens_to_transfer: u64,
+    min_amount_out: u64,
 ) -> ProgramResult {
 
     // Validate tokens to transfer
@@ -40,34 +447,124 @@ pub fn process(
 
     // Get all the accounts that we need
 
-    // User account that is initiating the exchange
+    // User account that is initiating the exchange. It pays for its volume account if needed
     let user_account = next_account_info(accounts_iter)?;
-    assert_with_msg(user_account.is_signer, ProgramError::MissingRequiredSignature, "The user account must be a signer");
+    assert_signer(user_account, "The user account must be a signer")?;
+
+    // User token accounts. The source account's mint picks the direction of the exchange
+    let user_source_account = next_account_info(accounts_iter)?;
+    assert_writable(user_source_account, "The user source token account must be writable")?;
+
+    let user_destination_account = next_account_info(accounts_iter)?;
+    assert_writable(user_destination_account, "The user destination token account must be writable")?;
+
+    // Exchange booth, vaults, mints and oracle
+    let booth = BoothAccounts::next(accounts_iter)?;
+    assert_unique(
+        &[user_source_account, user_destination_account, booth.vault_a, booth.vault_b],
+        "User token accounts and Vaults must all be different accounts",
+    )?;
+
+    let token_program = next_account_info(accounts_iter)?;
+    assert_token_program(token_program, &[booth.mint_a, booth.mint_b])?;
+
+    let protocol_config_acct = next_account_info(accounts_iter)?;
+    let protocol_config = ProtocolConfig::load_or_default(protocol_config_acct, program_id)?;
+
+    let user_volume_acct = next_account_info(accounts_iter)?;
+    let system_program = next_account_info(accounts_iter)?;
+
+    // Allowlist entry or gating token account, only looked at if the booth is access gated
+    let access_acct = next_account_info(accounts_iter)?;
 
-    // User token accounts
-    let user_token_a_account = next_account_info(accounts_iter)?;
-    assert_with_msg(user_token_a_account.is_writable, ProgramError::MissingRequiredSignature, "The user token A account must be writable");
+    let source_mint = booth.user_account_mint(program_id, user_account.key, user_source_account)?;
+    let leg = quote(program_id, &booth, &source_mint, tokens_to_transfer)?;
+    check_access(program_id, &booth, &leg, user_account, access_acct)?;
+    let mut leg = leg.into_mut(program_id, &booth)?;
+    record_volume(program_id, &booth, &mut leg, user_account, user_volume_acct, system_program)?;
 
-    let user_token_b_account = next_account_info(accounts_iter)?;
-    assert_with_msg(user_token_b_account.is_writable, ProgramError::MissingRequiredSignature, "The user token B account must be writable");
+    let destination_mint = booth.user_account_mint(program_id, user_account.key, user_destination_account)?;
+    assert_with_msg(
+        destination_mint == leg.output_mint(),
+        ExchangeBoothError::InvalidAccountData.into(),
+        "The user destination token account has the wrong mint",
+    )?;
 
-    // Vault accounts that are PDAs
-    let vault_a_pda = next_account_info(accounts_iter)?;
-    let vault_a_pda_acc_info = TokenAccount::unpack(&vault_a_pda.data.borrow()).map_err(|_| ProgramError::InvalidAccountData)?;
+    assert_with_msg(
+        leg.amount_out >= min_amount_out,
+        ExchangeBoothError::SlippageExceeded.into(),
+        "Exchange would return less than the minimum amount out",
+    )?;
+
+    // To pay in or get paid in lamports the user passes their wrapped SOL PDA instead of a
+    // token account. It is opened here and closed back into the user's system account below.
+    let (wsol, _) = token::find_wrapped_sol_address(booth.exchange_booth.key, user_account.key, program_id);
+    let wsol_account = [user_source_account, user_destination_account]
+        .into_iter()
+        .find(|ai| *ai.key == wsol);
+    if let Some(wsol_account) = wsol_account {
+        let lamports = if wsol_account.key == user_source_account.key { tokens_to_transfer } else { 0 };
+        token::wrap_sol(
+            program_id,
+            booth.exchange_booth.key,
+            user_account,
+            wsol_account,
+            booth.native_mint()?,
+            token_program,
+            system_program,
+            lamports,
+        )?;
+    }
+
+    // Move the input tokens into the booth, then pay the user out of the other vault
+    token::transfer_checked(
+        token_program,
+        user_source_account,
+        leg.input_mint_account(&booth),
+        leg.input_vault,
+        user_account,
+        tokens_to_transfer,
+        &[],
+    )?;
+
+    pay_out(program_id, &booth, &leg, user_destination_account, token_program, leg.payout)?;
+
+    // An optional trailing referrer token account gets a cut of the fee
+    let referrer_account = accounts_iter.next();
+    if let Some(referrer_account) = referrer_account {
+        assert_writable(referrer_account, "The referrer token account must be writable")?;
+
+        let referrer = unpack_token_account(referrer_account)?;
+        assert_with_msg(
+            referrer.mint == leg.output_mint(),
+            ExchangeBoothError::InvalidAccountData.into(),
+            "The referrer token account has the wrong mint",
+        )?;
+
+        leg.referral_fee = (leg.fee as u128 * leg.exchange_booth.referral_share_bps as u128 / 10_000) as u64;
+        if leg.referral_fee > 0 {
+            pay_out(program_id, &booth, &leg, referrer_account, token_program, leg.referral_fee)?;
+        }
+        msg!("Referral: {} tokens to {}.", leg.referral_fee, referrer_account.key);
+    }
+
+    record_fee(&mut leg, &protocol_config)?;
+
+    if let Some(wsol_account) = wsol_account {
+        token::unwrap_sol(token_program, wsol_account, user_account)?;
+    }
 
-    let vault_b_pda = next_account_info(accounts_iter)?;
-    let vault_b_pda_acc_info = TokenAccount::unpack(&vault_b_pda.data.borrow()).map_err(|_| ProgramError::InvalidAccountData)?;
+    msg!("Exchanged {} tokens for {} tokens.", tokens_to_transfer, leg.amount_out);
 
-    // Mint accounts
-    let mint_a_acc = next_account_info(accounts_iter)?;
-    let vault_a_pda_acc_info = Mint::unpack(&mint_a_acc.data.borrow()).map_err(|_| ProgramError::InvalidAccountData)?;
-    let mint_b_acc = next_account_info(accounts_iter)?;
-    let vault_b_pda_acc_info = Mint::unpack(&mint_b_acc.data.borrow()).map_err(|_| ProgramError::InvalidAccountData)?;
+    leg.event(booth.exchange_booth.key, user_account.key, referrer_account.map(|acct| *acct.key)).emit();
 
-    // Get the exchange booth account
-    let exchange_booth_acc = next_account_info(accounts_iter)?;
-    let exchange_booth = ExchangeBooth::try_from_slice(&exchange_booth_acc.data.borrow())?;
+    let result = ExchangeResult {
+        amount_in: leg.amount_in,
+        amount_out: leg.amount_out,
+        fee: leg.fee,
+        price: leg.price,
+    };
+    set_return_data(&result.try_to_vec()?);
 
-    // Validate the mint accounts with the corresponding mint accounts in the exchange booth
     Ok(())
 }
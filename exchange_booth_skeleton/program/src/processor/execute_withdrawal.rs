@@ -0,0 +1,82 @@
+use solana_program::{
+    account_info::{AccountInfo, next_account_info},
+    clock::Clock,
+    entrypoint::ProgramResult, msg,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvar::Sysvar,
+};
+
+use crate::{
+    error::ExchangeBoothError,
+    processor::withdraw::send_from_vault,
+    state::{ExchangeBooth, QueuedWithdrawal, PAUSE_WITHDRAWALS},
+    validation::{assert_key, assert_owned_by, assert_unique, assert_with_msg},
+};
+
+pub fn process(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+    let exchange_booth_acct = next_account_info(accounts_iter)?;
+    let queued_withdrawal_acct = next_account_info(accounts_iter)?;
+    let vault = next_account_info(accounts_iter)?;
+    let mint = next_account_info(accounts_iter)?;
+    let destination = next_account_info(accounts_iter)?;
+    let payer = next_account_info(accounts_iter)?;
+    let token_program = next_account_info(accounts_iter)?;
+
+    assert_unique(
+        &[destination, vault],
+        "The destination can not be the Vault.",
+    )?;
+
+    assert_owned_by(
+        exchange_booth_acct,
+        program_id,
+        "Exchange Booth and queued withdrawal must be owned by the Exchange Booth program.",
+    )?;
+    assert_owned_by(
+        queued_withdrawal_acct,
+        program_id,
+        "Exchange Booth and queued withdrawal must be owned by the Exchange Booth program.",
+    )?;
+
+    let exchange_booth = ExchangeBooth::load(exchange_booth_acct, program_id)?;
+    exchange_booth.check_not_paused(PAUSE_WITHDRAWALS)?;
+
+    let queued_withdrawal = QueuedWithdrawal::load(queued_withdrawal_acct)?;
+
+    assert_with_msg(
+        queued_withdrawal.exchange_booth == *exchange_booth_acct.key,
+        ExchangeBoothError::InvalidAccountData.into(),
+        "Queued withdrawal belongs to a different Exchange Booth.",
+    )?;
+
+    assert_key(vault, &queued_withdrawal.vault, "Vault does not match the queued withdrawal.")?;
+    assert_key(destination, &queued_withdrawal.destination, "Destination does not match the queued withdrawal.")?;
+    assert_key(payer, &queued_withdrawal.payer, "Payer does not match the queued withdrawal.")?;
+
+    // anyone can execute the withdrawal once the delay is over
+    assert_with_msg(
+        Clock::get()?.slot >= queued_withdrawal.earliest_execute_slot,
+        ExchangeBoothError::WithdrawalTimelocked.into(),
+        "Queued withdrawal can not be executed yet.",
+    )?;
+
+    send_from_vault(program_id, exchange_booth_acct, &exchange_booth, vault, mint, destination, token_program, queued_withdrawal.amount)?;
+
+    // close the queued withdrawal and refund its rent
+    let withdrawal_lamports = queued_withdrawal_acct.lamports();
+    **queued_withdrawal_acct.lamports.borrow_mut() = 0;
+    **payer.lamports.borrow_mut() = payer
+        .lamports()
+        .checked_add(withdrawal_lamports)
+        .ok_or(ProgramError::InvalidArgument)?;
+    queued_withdrawal_acct.data.borrow_mut().fill(0);
+
+    msg!("Executed withdrawal of {} tokens to '{}'.", queued_withdrawal.amount, destination.key);
+
+    Ok(())
+}
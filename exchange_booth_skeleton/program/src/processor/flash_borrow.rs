@@ -0,0 +1,124 @@
+use borsh::BorshDeserialize;
+use solana_program::{
+    account_info::{AccountInfo, next_account_info},
+    entrypoint::ProgramResult, msg,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvar::instructions::{load_current_index_checked, load_instruction_at_checked},
+};
+
+use crate::{
+    error::ExchangeBoothError,
+    instruction::ExchangeBoothInstruction,
+    state::{ExchangeBooth, PAUSE_EXCHANGES},
+    token::{self, assert_token_program},
+    validation::{assert_owned_by, assert_pda, assert_signer, assert_unique, assert_with_msg},
+};
+
+pub fn process(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    amount: u64,
+) -> ProgramResult {
+    assert_with_msg(amount > 0, ProgramError::InvalidArgument, "The number of tokens to borrow must be > 0")?;
+
+    let accounts_iter = &mut accounts.iter();
+    let borrower = next_account_info(accounts_iter)?;
+    let borrower_token_account = next_account_info(accounts_iter)?;
+    let exchange_booth_acct = next_account_info(accounts_iter)?;
+    let vault = next_account_info(accounts_iter)?;
+    let mint = next_account_info(accounts_iter)?;
+    let token_program = next_account_info(accounts_iter)?;
+    let instructions_sysvar = next_account_info(accounts_iter)?;
+
+    assert_unique(
+        &[borrower_token_account, vault],
+        "The borrower token account can not be the Vault.",
+    )?;
+
+    assert_signer(borrower, "First account passed for 'Borrower' is not a signer as is required.")?;
+
+    assert_owned_by(
+        exchange_booth_acct,
+        program_id,
+        "'Exchange Booth' Account is not owned by the Exchange Booth program.",
+    )?;
+
+    let mut exchange_booth = ExchangeBooth::load_mut(exchange_booth_acct, program_id)?;
+    exchange_booth.check_not_paused(PAUSE_EXCHANGES)?;
+
+    assert_with_msg(
+        exchange_booth.flash_loan_due == 0,
+        ExchangeBoothError::FlashLoanOutstanding.into(),
+        "The Exchange Booth already has an outstanding flash loan.",
+    )?;
+
+    let (seed, booth_mint): (&[u8], Pubkey) = if *vault.key == exchange_booth.vault_a {
+        (b"vault_a", exchange_booth.mint_a)
+    } else if *vault.key == exchange_booth.vault_b {
+        (b"vault_b", exchange_booth.mint_b)
+    } else {
+        msg!("Vault passed in is not a Vault of the Exchange Booth.");
+        return Err(ExchangeBoothError::InvalidAccountAddress.into());
+    };
+
+    assert_with_msg(
+        *mint.key == booth_mint,
+        ExchangeBoothError::InvalidAccountAddress.into(),
+        "Mint passed in is not the mint of the Vault.",
+    )?;
+    assert_token_program(token_program, &[mint])?;
+
+    let fee = (amount as u128 * exchange_booth.flash_loan_fee_bps as u128 / 10_000) as u64;
+    let amount_due = amount.checked_add(fee).ok_or(ProgramError::InvalidArgument)?;
+
+    // The repayment has to be a later top level instruction of this same transaction
+    let current_index = load_current_index_checked(instructions_sysvar)? as usize;
+    let current_ix = load_instruction_at_checked(current_index, instructions_sysvar)?;
+    assert_with_msg(
+        current_ix.program_id == *program_id,
+        ProgramError::InvalidArgument,
+        "FlashBorrow can not be called through a CPI.",
+    )?;
+
+    let mut repaid = false;
+    let mut index = current_index + 1;
+    while let Ok(ix) = load_instruction_at_checked(index, instructions_sysvar) {
+        if ix.program_id == *program_id && ix.accounts.len() >= 4 {
+            if let Ok(ExchangeBoothInstruction::FlashRepay { amount: repay_amount }) =
+                ExchangeBoothInstruction::try_from_slice(&ix.data)
+            {
+                if ix.accounts[2].pubkey == *exchange_booth_acct.key
+                    && ix.accounts[3].pubkey == *vault.key
+                    && repay_amount >= amount_due
+                {
+                    repaid = true;
+                    break;
+                }
+            }
+        }
+        index += 1;
+    }
+
+    assert_with_msg(
+        repaid,
+        ExchangeBoothError::FlashLoanNotRepaid.into(),
+        "No matching FlashRepay for principal plus fee later in the transaction.",
+    )?;
+
+    exchange_booth.flash_loan_vault = *vault.key;
+    exchange_booth.flash_loan_due = amount_due;
+
+    let bump = assert_pda(
+        vault,
+        Pubkey::find_program_address(&[seed, exchange_booth_acct.key.as_ref()], program_id),
+        "Vault key from Find PDA doesn't match passed in Vault addr.",
+    )?;
+
+    token::transfer_checked(token_program, vault, mint, borrower_token_account, vault, amount,
+        &[&[seed, exchange_booth_acct.key.as_ref(), &[bump]]])?;
+
+    msg!("Lent {} tokens, {} due back.", amount, amount_due);
+
+    Ok(())
+}
@@ -0,0 +1,71 @@
+use solana_program::{
+    account_info::{AccountInfo, next_account_info},
+    entrypoint::ProgramResult, msg,
+    pubkey::Pubkey,
+};
+
+use crate::{
+    error::ExchangeBoothError,
+    state::ExchangeBooth,
+    token::{self, assert_token_program},
+    validation::{assert_owned_by, assert_signer, assert_unique, assert_with_msg},
+};
+
+pub fn process(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    amount: u64,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+    let borrower = next_account_info(accounts_iter)?;
+    let borrower_token_account = next_account_info(accounts_iter)?;
+    let exchange_booth_acct = next_account_info(accounts_iter)?;
+    let vault = next_account_info(accounts_iter)?;
+    let mint = next_account_info(accounts_iter)?;
+    let token_program = next_account_info(accounts_iter)?;
+
+    assert_unique(
+        &[borrower_token_account, vault],
+        "The borrower token account can not be the Vault.",
+    )?;
+
+    assert_signer(borrower, "First account passed for 'Borrower' is not a signer as is required.")?;
+
+    assert_owned_by(
+        exchange_booth_acct,
+        program_id,
+        "'Exchange Booth' Account is not owned by the Exchange Booth program.",
+    )?;
+
+    let mut exchange_booth = ExchangeBooth::load_mut(exchange_booth_acct, program_id)?;
+
+    assert_with_msg(
+        exchange_booth.flash_loan_due > 0 && exchange_booth.flash_loan_vault == *vault.key,
+        ExchangeBoothError::InvalidAccountAddress.into(),
+        "There is no outstanding flash loan from this Vault.",
+    )?;
+
+    let booth_mint = if *vault.key == exchange_booth.vault_a { exchange_booth.mint_a } else { exchange_booth.mint_b };
+    assert_with_msg(
+        *mint.key == booth_mint,
+        ExchangeBoothError::InvalidAccountAddress.into(),
+        "Mint passed in is not the mint of the Vault.",
+    )?;
+    assert_token_program(token_program, &[mint])?;
+
+    // what reaches the vault after any transfer fee has to cover the loan
+    assert_with_msg(
+        amount - token::transfer_fee(mint, amount)? >= exchange_booth.flash_loan_due,
+        ExchangeBoothError::FlashLoanNotRepaid.into(),
+        "Repayment is less than principal plus fee.",
+    )?;
+
+    token::transfer_checked(token_program, borrower_token_account, mint, vault, borrower, amount, &[])?;
+
+    exchange_booth.flash_loan_vault = Pubkey::default();
+    exchange_booth.flash_loan_due = 0;
+
+    msg!("Flash loan repaid with {} tokens.", amount);
+
+    Ok(())
+}
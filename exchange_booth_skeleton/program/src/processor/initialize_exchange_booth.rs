@@ -1,44 +1,31 @@
 use solana_program::{
-    system_instruction::create_account,
-    system_instruction,
     sysvar::{rent::Rent, Sysvar},
     account_info::{AccountInfo, next_account_info},
-    program_pack::{Pack},
     entrypoint::ProgramResult, msg, 
     program_error::ProgramError,
     pubkey::Pubkey,
-    program::{invoke_signed, invoke},
-};
-
-use spl_token::{
-    id, instruction, state::Account
+    program::invoke_signed,
 };
 
 use crate::{
-    error::ExchangeBoothError,
-    state::ExchangeBooth,
+    account::{create_pda_account, grow},
+    events::BoothEvent,
+    state::{BoothRegistry, ExchangeBooth, RegistryIndex},
+    token::{assert_token_program, check_mint_extensions, token_account_len},
+    validation::{assert_owned_by, assert_pda, assert_signer, assert_with_msg},
 };
 
-use borsh::{BorshDeserialize, BorshSerialize};
-
-pub fn assert_with_msg(statement: bool, err: ProgramError, msg: &str) -> ProgramResult {
-    if !statement {
-        msg!(msg);
-        Err(err)
-    } else {
-        Ok(())
-    }
-}
-
 pub fn process(
     program_id: &Pubkey,
-    accounts: &[AccountInfo]
-    // ???
+    accounts: &[AccountInfo],
+    spread_bps: u64,
+    flash_loan_fee_bps: u64,
+    referral_share_bps: u64,
+    withdrawal_limit: u64,
+    withdrawal_window_slots: u64,
 ) -> ProgramResult {
-    msg!("Instruction: InititializeExchangeBooth");
     let accounts_iter = &mut accounts.iter();
 
-    let vault_size: usize = Account::LEN;
     // get accounts
     let administrator_ai = next_account_info(accounts_iter)?;
     let exchange_booth_ai = next_account_info(accounts_iter)?;
@@ -50,13 +37,33 @@ pub fn process(
     let system_program_ai = next_account_info(accounts_iter)?;
     let token_program_ai = next_account_info(accounts_iter)?;
     let rent_account_ai = next_account_info(accounts_iter)?;
+    let registry_ai = next_account_info(accounts_iter)?;
+    let registry_index_ai = next_account_info(accounts_iter)?;
     
     assert_with_msg(
-        administrator_ai.is_signer,
-        ProgramError::MissingRequiredSignature,
+        spread_bps <= 10_000 && flash_loan_fee_bps <= 10_000 && referral_share_bps <= 10_000,
+        ProgramError::InvalidArgument,
+        "spread_bps, flash_loan_fee_bps and referral_share_bps must be at most 10000.",
+    )?;
+    assert_with_msg(
+        withdrawal_limit == 0 || withdrawal_window_slots > 0,
+        ProgramError::InvalidArgument,
+        "A non-zero withdrawal_limit needs a non-zero withdrawal_window_slots.",
+    )?;
+
+    assert_signer(
+        administrator_ai,
         "first account passed 'administrator' is not a signer as is required.",
     )?;
 
+    // refuse to re-initialize an existing booth before any vault gets created
+    let mut exchange_booth = ExchangeBooth::load_uninitialized(exchange_booth_ai, program_id)?;
+
+    // both mints belong to the same token program, legacy or Token-2022
+    assert_token_program(token_program_ai, &[mint_a_ai, mint_b_ai])?;
+    check_mint_extensions(mint_a_ai)?;
+    check_mint_extensions(mint_b_ai)?;
+
     // get PDAs of vaults
     let(vault_a_key, vault_a_bump) = Pubkey::find_program_address(
         &[
@@ -68,9 +75,9 @@ pub fn process(
     
     msg!("vault a pda: '{:?}', bump: {}", vault_a_key, vault_a_bump);
 
-    assert_with_msg(
-        vault_a_key == *vault_a_ai.key,
-        ProgramError::InvalidArgument,
+    assert_pda(
+        vault_a_ai,
+        (vault_a_key, vault_a_bump),
         "Key returned from find_program_address (while creating PDA) was not equal to the key passed as the 'vault_a' Account.",
     )?;
 
@@ -84,45 +91,44 @@ pub fn process(
 
     msg!("vault b pda: '{:?}', bump: {}", vault_b_key, vault_b_bump);
 
-    assert_with_msg(
-        vault_b_key == *vault_b_ai.key,
-        ProgramError::InvalidArgument,
+    assert_pda(
+        vault_b_ai,
+        (vault_b_key, vault_b_bump),
         "Key returned from find_program_address (while creating PDA) was not equal to the key passed as the 'vault_b' Account.",
     )?;
 
-    let ix_create_vault_a = system_instruction::create_account(
-       administrator_ai.key,
-       vault_a_ai.key,
-       Rent::get()?.minimum_balance(vault_size),
-       vault_size as u64,
-       token_program_ai.key
-    );
+    // Token-2022 vaults need room for the account extensions their mint requires
+    let vault_a_size = token_account_len(mint_a_ai)?;
+    let vault_b_size = token_account_len(mint_b_ai)?;
+
+    // signers = same keys that went into pda
+    create_pda_account(
+        administrator_ai,
+        vault_a_ai,
+        Rent::get()?.minimum_balance(vault_a_size),
+        vault_a_size,
+        token_program_ai.key,
+        system_program_ai,
+        &[b"vault_a", exchange_booth_ai.key.as_ref(), &[vault_a_bump]],
+    )?;
 
-    let ix_create_vault_b = system_instruction::create_account(
-        administrator_ai.key,
-        vault_b_ai.key,
-        Rent::get()?.minimum_balance(vault_size),
-        vault_size as u64,
-        token_program_ai.key
-     );
-
-     // need all accts passed into instruction
-     // signers = same keys that went into pda
-     invoke_signed(&ix_create_vault_a,
-        &[administrator_ai.clone(), vault_a_ai.clone(), system_program_ai.clone()], 
-        &[&[b"vault_a", exchange_booth_ai.key.as_ref(), &[vault_a_bump]]])?;
-
-    invoke_signed(&ix_create_vault_b,
-        &[administrator_ai.clone(), vault_b_ai.clone(), system_program_ai.clone()], 
-        &[&[b"vault_b", exchange_booth_ai.key.as_ref(), &[vault_b_bump]]])?;
-
-     let ix_init_acct_vault_a = spl_token::instruction::initialize_account(
+    create_pda_account(
+        administrator_ai,
+        vault_b_ai,
+        Rent::get()?.minimum_balance(vault_b_size),
+        vault_b_size,
+        token_program_ai.key,
+        system_program_ai,
+        &[b"vault_b", exchange_booth_ai.key.as_ref(), &[vault_b_bump]],
+    )?;
+
+     let ix_init_acct_vault_a = spl_token_2022::instruction::initialize_account(
          token_program_ai.key, 
          &vault_a_key, 
          mint_a_ai.key, 
          &vault_a_key)?;
 
-     let ix_init_acct_vault_b = spl_token::instruction::initialize_account(
+     let ix_init_acct_vault_b = spl_token_2022::instruction::initialize_account(
         token_program_ai.key, 
         &vault_b_key, 
         mint_b_ai.key, 
@@ -136,13 +142,132 @@ pub fn process(
                 &[token_program_ai.clone(), vault_b_ai.clone(), mint_b_ai.clone(), rent_account_ai.clone()], 
                 &[&[b"vault_b", exchange_booth_ai.key.as_ref(), &[vault_b_bump]]])?;  
 
-     let mut exchange_booth = ExchangeBooth::load_unchecked(exchange_booth_ai)?;
+                exchange_booth.initialized = 1;
                 exchange_booth.admin = *administrator_ai.key;
                 exchange_booth.mint_a = *mint_a_ai.key;
                 exchange_booth.mint_b = *mint_b_ai.key;
                 exchange_booth.vault_a = *vault_a_ai.key;
                 exchange_booth.vault_b = *vault_b_ai.key;
                 exchange_booth.oracle = *oracle_ai.key;
-                exchange_booth.save(exchange_booth_ai)?;
+                exchange_booth.spread_bps = spread_bps;
+                exchange_booth.flash_loan_fee_bps = flash_loan_fee_bps;
+                exchange_booth.referral_share_bps = referral_share_bps;
+                exchange_booth.withdrawal_limit = withdrawal_limit;
+                exchange_booth.withdrawal_window_slots = withdrawal_window_slots;
+
+    register_booth(
+        program_id,
+        administrator_ai,
+        registry_ai,
+        registry_index_ai,
+        system_program_ai,
+        exchange_booth_ai.key,
+        mint_a_ai.key,
+        mint_b_ai.key,
+    )?;
+
+    BoothEvent::Initialized {
+        exchange_booth: *exchange_booth_ai.key,
+        admin: exchange_booth.admin,
+        mint_a: exchange_booth.mint_a,
+        mint_b: exchange_booth.mint_b,
+        oracle: exchange_booth.oracle,
+        spread_bps,
+        flash_loan_fee_bps,
+        referral_share_bps,
+    }
+    .emit();
+
     Ok(())
 }
+
+/// Adds `exchange_booth` to the registry of its mint pair, growing it by one key. The pair's
+/// first booth creates the registry and lists it in the registry index. `payer` covers the rent.
+#[allow(clippy::too_many_arguments)]
+pub fn register_booth<'info>(
+    program_id: &Pubkey,
+    payer: &AccountInfo<'info>,
+    registry_ai: &AccountInfo<'info>,
+    registry_index_ai: &AccountInfo<'info>,
+    system_program_ai: &AccountInfo<'info>,
+    exchange_booth: &Pubkey,
+    mint_a: &Pubkey,
+    mint_b: &Pubkey,
+) -> ProgramResult {
+    let registry_bump = assert_pda(
+        registry_ai,
+        BoothRegistry::find_address(mint_a, mint_b, program_id),
+        "Key returned from find_program_address was not equal to the key passed as the 'registry' Account.",
+    )?;
+
+    let mut registry = if registry_ai.data_is_empty() {
+        msg!("Registry for this mint pair does not exist yet. Creating it.");
+        let (mint_a, mint_b) = BoothRegistry::sorted_pair(mint_a, mint_b);
+        create_pda_account(
+            payer,
+            registry_ai,
+            Rent::get()?.minimum_balance(BoothRegistry::len(1)),
+            BoothRegistry::len(1),
+            program_id,
+            system_program_ai,
+            &[b"registry", mint_a.as_ref(), mint_b.as_ref(), &[registry_bump]],
+        )?;
+        list_registry(program_id, payer, registry_index_ai, system_program_ai, registry_ai.key)?;
+        BoothRegistry {
+            mint_a,
+            mint_b,
+            booths: Vec::new(),
+        }
+    } else {
+        assert_owned_by(
+            registry_ai,
+            program_id,
+            "'registry' Account is not owned by the Exchange Booth program.",
+        )?;
+        let registry = BoothRegistry::load(registry_ai)?;
+        grow(payer, registry_ai, system_program_ai, BoothRegistry::len(registry.booths.len() + 1))?;
+        registry
+    };
+    registry.booths.push(*exchange_booth);
+    registry.save(registry_ai)
+}
+
+/// Appends a newly created pair registry to the registry index, creating the index for the
+/// program's first pair.
+fn list_registry<'info>(
+    program_id: &Pubkey,
+    payer: &AccountInfo<'info>,
+    registry_index_ai: &AccountInfo<'info>,
+    system_program_ai: &AccountInfo<'info>,
+    registry: &Pubkey,
+) -> ProgramResult {
+    let index_bump = assert_pda(
+        registry_index_ai,
+        RegistryIndex::find_address(program_id),
+        "Key returned from find_program_address was not equal to the key passed as the 'registry_index' Account.",
+    )?;
+
+    let mut registry_index = if registry_index_ai.data_is_empty() {
+        create_pda_account(
+            payer,
+            registry_index_ai,
+            Rent::get()?.minimum_balance(RegistryIndex::len(1)),
+            RegistryIndex::len(1),
+            program_id,
+            system_program_ai,
+            &[b"registry_index", &[index_bump]],
+        )?;
+        RegistryIndex::default()
+    } else {
+        assert_owned_by(
+            registry_index_ai,
+            program_id,
+            "'registry_index' Account is not owned by the Exchange Booth program.",
+        )?;
+        let registry_index = RegistryIndex::load(registry_index_ai)?;
+        grow(payer, registry_index_ai, system_program_ai, RegistryIndex::len(registry_index.registries.len() + 1))?;
+        registry_index
+    };
+    registry_index.registries.push(*registry);
+    registry_index.save(registry_index_ai)
+}
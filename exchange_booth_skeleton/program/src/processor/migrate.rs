@@ -0,0 +1,91 @@
+use borsh::BorshDeserialize;
+use bytemuck::Zeroable;
+use solana_program::{
+    account_info::{AccountInfo, next_account_info},
+    entrypoint::ProgramResult, msg,
+    pubkey::Pubkey,
+};
+
+use crate::{
+    account::grow,
+    error::ExchangeBoothError,
+    processor::initialize_exchange_booth::register_booth,
+    state::{ExchangeBooth, ACCOUNT_HEADER_LEN},
+    validation::{assert_owned_by, assert_signer, assert_with_msg, assert_writable},
+};
+
+pub fn process(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+    let payer = next_account_info(accounts_iter)?;
+    let exchange_booth_acct = next_account_info(accounts_iter)?;
+    let system_program = next_account_info(accounts_iter)?;
+    let registry = next_account_info(accounts_iter)?;
+    let registry_index = next_account_info(accounts_iter)?;
+
+    assert_signer(payer, "First account passed for 'Payer' is not a signer as is required.")?;
+    assert_writable(exchange_booth_acct, "Second account passed for 'Exchange Booth' must be writable.")?;
+    assert_owned_by(
+        exchange_booth_acct,
+        program_id,
+        "'Exchange Booth' Account is not owned by the Exchange Booth program.",
+    )?;
+
+    if ExchangeBooth::load(exchange_booth_acct, program_id).is_ok() {
+        msg!("Exchange Booth '{}' is already on the current layout.", exchange_booth_acct.key);
+        return Ok(());
+    }
+
+    // The first booths were the Borsh encoded initialized flag, which was never set, and six
+    // keys. Their vaults are the booth's vault PDAs, which tells a booth the first
+    // InititializeExchangeBooth wrote from an account that merely has the same size.
+    let mut exchange_booth = ExchangeBooth::zeroed();
+    {
+        let data = exchange_booth_acct.data.borrow();
+        assert_with_msg(
+            data.len() == ExchangeBooth::UNVERSIONED_LEN,
+            ExchangeBoothError::InvalidAccountData.into(),
+            "Account is neither a current Exchange Booth nor one from before booths had a header.",
+        )?;
+        let keys = &mut &data[1..];
+        exchange_booth.initialized = 1;
+        exchange_booth.admin = Pubkey::deserialize(keys)?;
+        exchange_booth.mint_a = Pubkey::deserialize(keys)?;
+        exchange_booth.mint_b = Pubkey::deserialize(keys)?;
+        exchange_booth.vault_a = Pubkey::deserialize(keys)?;
+        exchange_booth.vault_b = Pubkey::deserialize(keys)?;
+        exchange_booth.oracle = Pubkey::deserialize(keys)?;
+    }
+    assert_with_msg(
+        exchange_booth.vault_a == ExchangeBooth::find_vault_address(exchange_booth_acct.key, b"vault_a", program_id).0
+            && exchange_booth.vault_b
+                == ExchangeBooth::find_vault_address(exchange_booth_acct.key, b"vault_b", program_id).0,
+        ExchangeBoothError::InvalidAccountData.into(),
+        "Account's vaults are not the vault PDAs of an initialized Exchange Booth.",
+    )?;
+
+    // Everything the booth has gained since starts out zeroed, so a migrated booth has no
+    // spread and no withdrawal limit. It was never registered, so it joins its pair's registry.
+    register_booth(
+        program_id,
+        payer,
+        registry,
+        registry_index,
+        system_program,
+        exchange_booth_acct.key,
+        &exchange_booth.mint_a,
+        &exchange_booth.mint_b,
+    )?;
+
+    grow(payer, exchange_booth_acct, system_program, ExchangeBooth::LEN)?;
+
+    let mut data = exchange_booth_acct.data.borrow_mut();
+    data[0] = ExchangeBooth::DISCRIMINATOR;
+    data[1] = ExchangeBooth::VERSION;
+    data[ACCOUNT_HEADER_LEN..].copy_from_slice(bytemuck::bytes_of(&exchange_booth));
+
+    msg!("Migrated Exchange Booth '{}' to version {}.", exchange_booth_acct.key, ExchangeBooth::VERSION);
+    Ok(())
+}
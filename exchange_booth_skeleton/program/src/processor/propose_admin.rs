@@ -0,0 +1,43 @@
+use solana_program::{
+    account_info::{AccountInfo, next_account_info},
+    entrypoint::ProgramResult, msg,
+    pubkey::Pubkey,
+};
+
+use crate::{
+    state::{AdminMultisig, ExchangeBooth},
+    validation::assert_owned_by,
+};
+
+pub fn process(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    new_admin: Pubkey,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+    let admin = next_account_info(accounts_iter)?;
+    let exchange_booth_acct = next_account_info(accounts_iter)?;
+
+    assert_owned_by(
+        exchange_booth_acct,
+        program_id,
+        "'Exchange Booth' Account is not owned by the Exchange Booth program.",
+    )?;
+
+    let mut exchange_booth = ExchangeBooth::load_mut(exchange_booth_acct, program_id)?;
+
+    AdminMultisig::authorize(
+        program_id,
+        exchange_booth_acct.key,
+        &exchange_booth.admin,
+        admin,
+        accounts_iter.as_slice(),
+    )?;
+
+    // proposing the default key cancels a pending proposal
+    exchange_booth.pending_admin = new_admin;
+
+    msg!("Proposed '{}' as the new Admin.", new_admin);
+
+    Ok(())
+}
@@ -0,0 +1,91 @@
+use solana_program::{
+    account_info::{AccountInfo, next_account_info},
+    clock::Clock,
+    entrypoint::ProgramResult, msg,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvar::{rent::Rent, Sysvar},
+};
+
+use crate::{
+    account::create_pda_account,
+    error::ExchangeBoothError,
+    state::{AdminMultisig, ConfigChange, ExchangeBooth, QueuedConfigChange, MIN_CONFIG_CHANGE_DELAY_SLOTS},
+    validation::{assert_owned_by, assert_pda, assert_signer, assert_with_msg},
+};
+
+pub fn process(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    change_id: u64,
+    change: ConfigChange,
+    earliest_apply_slot: u64,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+    let admin = next_account_info(accounts_iter)?;
+    let exchange_booth_acct = next_account_info(accounts_iter)?;
+    let queued_change_acct = next_account_info(accounts_iter)?;
+    let payer = next_account_info(accounts_iter)?;
+    let system_program = next_account_info(accounts_iter)?;
+
+    change.validate()?;
+
+    assert_signer(payer, "Fourth account passed for 'Payer' is not a signer as is required.")?;
+
+    assert_owned_by(
+        exchange_booth_acct,
+        program_id,
+        "'Exchange Booth' Account is not owned by the Exchange Booth program.",
+    )?;
+
+    let exchange_booth = ExchangeBooth::load(exchange_booth_acct, program_id)?;
+
+    AdminMultisig::authorize(
+        program_id,
+        exchange_booth_acct.key,
+        &exchange_booth.admin,
+        admin,
+        accounts_iter.as_slice(),
+    )?;
+
+    let current_slot = Clock::get()?.slot;
+    assert_with_msg(
+        earliest_apply_slot >= current_slot.saturating_add(MIN_CONFIG_CHANGE_DELAY_SLOTS),
+        ProgramError::InvalidArgument,
+        "earliest_apply_slot is sooner than the minimum config change delay.",
+    )?;
+
+    let bump = assert_pda(
+        queued_change_acct,
+        QueuedConfigChange::find_address(exchange_booth_acct.key, change_id, program_id),
+        "Key returned from find_program_address was not equal to the key passed as the 'queued_change' Account.",
+    )?;
+
+    assert_with_msg(
+        queued_change_acct.data_is_empty(),
+        ExchangeBoothError::AccountAlreadyInitialized.into(),
+        "A config change with this id is already queued.",
+    )?;
+
+    create_pda_account(
+        payer,
+        queued_change_acct,
+        Rent::get()?.minimum_balance(QueuedConfigChange::LEN),
+        QueuedConfigChange::LEN,
+        program_id,
+        system_program,
+        &[b"config_change", exchange_booth_acct.key.as_ref(), &change_id.to_le_bytes(), &[bump]],
+    )?;
+
+    msg!("Queued {:?}, applicable from slot {}.", change, earliest_apply_slot);
+
+    QueuedConfigChange {
+        exchange_booth: *exchange_booth_acct.key,
+        payer: *payer.key,
+        earliest_apply_slot,
+        change,
+    }
+    .save(queued_change_acct)?;
+
+    Ok(())
+}
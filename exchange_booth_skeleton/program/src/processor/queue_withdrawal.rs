@@ -0,0 +1,96 @@
+use solana_program::{
+    account_info::{AccountInfo, next_account_info},
+    clock::Clock,
+    entrypoint::ProgramResult, msg,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvar::{rent::Rent, Sysvar},
+};
+
+use crate::{
+    account::create_pda_account,
+    error::ExchangeBoothError,
+    state::{AdminMultisig, ExchangeBooth, QueuedWithdrawal, MIN_WITHDRAWAL_DELAY_SLOTS},
+    validation::{assert_owned_by, assert_pda, assert_signer, assert_with_msg},
+};
+
+pub fn process(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    withdrawal_id: u64,
+    amount: u64,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+    let admin = next_account_info(accounts_iter)?;
+    let exchange_booth_acct = next_account_info(accounts_iter)?;
+    let queued_withdrawal_acct = next_account_info(accounts_iter)?;
+    let vault = next_account_info(accounts_iter)?;
+    let destination = next_account_info(accounts_iter)?;
+    let payer = next_account_info(accounts_iter)?;
+    let system_program = next_account_info(accounts_iter)?;
+
+    assert_with_msg(amount > 0, ProgramError::InvalidArgument, "The number of tokens to withdraw must be > 0")?;
+
+    assert_signer(payer, "Sixth account passed for 'Payer' is not a signer as is required.")?;
+
+    assert_owned_by(
+        exchange_booth_acct,
+        program_id,
+        "'Exchange Booth' Account is not owned by the Exchange Booth program.",
+    )?;
+
+    let exchange_booth = ExchangeBooth::load(exchange_booth_acct, program_id)?;
+
+    AdminMultisig::authorize(
+        program_id,
+        exchange_booth_acct.key,
+        &exchange_booth.admin,
+        admin,
+        accounts_iter.as_slice(),
+    )?;
+
+    assert_with_msg(
+        *vault.key == exchange_booth.vault_a || *vault.key == exchange_booth.vault_b,
+        ExchangeBoothError::InvalidAccountAddress.into(),
+        "Vault passed in is not a Vault of the Exchange Booth.",
+    )?;
+
+    let bump = assert_pda(
+        queued_withdrawal_acct,
+        QueuedWithdrawal::find_address(exchange_booth_acct.key, withdrawal_id, program_id),
+        "Key returned from find_program_address was not equal to the key passed as the 'queued_withdrawal' Account.",
+    )?;
+
+    assert_with_msg(
+        queued_withdrawal_acct.data_is_empty(),
+        ExchangeBoothError::AccountAlreadyInitialized.into(),
+        "A withdrawal with this id is already queued.",
+    )?;
+
+    create_pda_account(
+        payer,
+        queued_withdrawal_acct,
+        Rent::get()?.minimum_balance(QueuedWithdrawal::LEN),
+        QueuedWithdrawal::LEN,
+        program_id,
+        system_program,
+        &[b"withdrawal", exchange_booth_acct.key.as_ref(), &withdrawal_id.to_le_bytes(), &[bump]],
+    )?;
+
+    let earliest_execute_slot = Clock::get()?.slot.saturating_add(MIN_WITHDRAWAL_DELAY_SLOTS);
+
+    QueuedWithdrawal {
+        exchange_booth: *exchange_booth_acct.key,
+        payer: *payer.key,
+        vault: *vault.key,
+        destination: *destination.key,
+        amount,
+        earliest_execute_slot,
+    }
+    .save(queued_withdrawal_acct)?;
+
+    msg!("Queued withdrawal of {} tokens to '{}', executable from slot {}.",
+        amount, destination.key, earliest_execute_slot);
+
+    Ok(())
+}
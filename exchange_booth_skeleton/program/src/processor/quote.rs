@@ -0,0 +1,67 @@
+use borsh::BorshSerialize;
+use solana_program::{
+    account_info::{AccountInfo, next_account_info},
+    entrypoint::ProgramResult,
+    program::set_return_data,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+use crate::{
+    error::ExchangeBoothError,
+    instruction::QuoteResult,
+    processor::exchange::{check_access, quote, BoothAccounts},
+    token::assert_token_program,
+    validation::assert_with_msg,
+};
+
+pub fn process(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    tokens_to_transfer: u64,
+) -> ProgramResult {
+    assert_with_msg(tokens_to_transfer > 0, ProgramError::InvalidArgument, "The number of tokens to quote must be > 0")?;
+
+    // Same accounts as Exchange, so a client can simulate a Quote with the Exchange it is
+    // about to send. The user doesn't have to sign.
+    let accounts_iter = &mut accounts.iter();
+    let user_account = next_account_info(accounts_iter)?;
+    let user_source_account = next_account_info(accounts_iter)?;
+    let user_destination_account = next_account_info(accounts_iter)?;
+    let booth = BoothAccounts::next(accounts_iter)?;
+    let token_program = next_account_info(accounts_iter)?;
+    let _protocol_config = next_account_info(accounts_iter)?;
+    let _user_volume = next_account_info(accounts_iter)?;
+    let _system_program = next_account_info(accounts_iter)?;
+    let access_acct = next_account_info(accounts_iter)?;
+
+    assert_token_program(token_program, &[booth.mint_a, booth.mint_b])?;
+
+    let source_mint = booth.user_account_mint(program_id, user_account.key, user_source_account)?;
+    let leg = quote(program_id, &booth, &source_mint, tokens_to_transfer)?;
+    check_access(program_id, &booth, &leg, user_account, access_acct)?;
+
+    let destination_mint = booth.user_account_mint(program_id, user_account.key, user_destination_account)?;
+    assert_with_msg(
+        destination_mint == leg.output_mint(),
+        ExchangeBoothError::InvalidAccountData.into(),
+        "The user destination token account has the wrong mint",
+    )?;
+
+    // The oracle price doesn't move with size, so the only impact is the spread and any
+    // transfer fee of the output token
+    let gross_out = leg.payout as u128 + leg.fee as u128;
+    let shortfall = gross_out - leg.amount_out as u128;
+    let price_impact_bps = (shortfall * 10_000).checked_div(gross_out).unwrap_or(0) as u64;
+
+    let result = QuoteResult {
+        amount_in: leg.amount_in,
+        amount_out: leg.amount_out,
+        fee: leg.fee,
+        price: leg.price,
+        price_impact_bps,
+    };
+    set_return_data(&result.try_to_vec()?);
+
+    Ok(())
+}
@@ -0,0 +1,126 @@
+use solana_program::{
+    account_info::{AccountInfo, next_account_info},
+    entrypoint::ProgramResult, msg,
+    pubkey::Pubkey,
+    program_error::ProgramError
+};
+
+use crate::{
+    error::ExchangeBoothError,
+    processor::exchange::{check_access, pay_out, quote, record_fee, record_volume, BoothAccounts},
+    state::ProtocolConfig,
+    token::{self, assert_token_program, unpack_token_account},
+    validation::{assert_signer, assert_unique, assert_with_msg, assert_writable},
+};
+
+pub fn process(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    amount_in: u64,
+    min_amount_out: u64,
+) -> ProgramResult {
+    assert_with_msg(amount_in > 0, ProgramError::InvalidArgument, "The number of tokens to transfer must be > 0")?;
+
+    let accounts_iter = &mut accounts.iter();
+    let user_account = next_account_info(accounts_iter)?;
+    let user_source_account = next_account_info(accounts_iter)?;
+    let user_destination_account = next_account_info(accounts_iter)?;
+    let token_program = next_account_info(accounts_iter)?;
+    let protocol_config_acct = next_account_info(accounts_iter)?;
+    let system_program = next_account_info(accounts_iter)?;
+
+
+    assert_signer(user_account, "The user account must be a signer")?;
+    assert_writable(user_source_account, "The user source token account must be writable")?;
+    assert_writable(user_destination_account, "The user destination token account must be writable")?;
+    let protocol_config = ProtocolConfig::load_or_default(protocol_config_acct, program_id)?;
+
+    // Everything after the fixed accounts is one group of booth accounts plus the user's
+    // volume and access accounts per hop
+    let hop_accounts = accounts_iter.as_slice();
+    let accounts_per_hop = BoothAccounts::LEN + 2;
+    // `is_multiple_of` is newer than the rustc of the SBF toolchain
+    #[allow(unknown_lints, clippy::manual_is_multiple_of)]
+    assert_with_msg(
+        !hop_accounts.is_empty() && hop_accounts.len() % accounts_per_hop == 0,
+        ProgramError::NotEnoughAccountKeys,
+        "Route accounts must be a non-empty list of booth account groups",
+    )?;
+    let hops_iter = &mut hop_accounts.iter();
+    let mut booths = Vec::with_capacity(hop_accounts.len() / accounts_per_hop);
+    let mut user_accts = Vec::with_capacity(hop_accounts.len() / accounts_per_hop);
+    while hops_iter.len() > 0 {
+        booths.push(BoothAccounts::next(hops_iter)?);
+        user_accts.push((next_account_info(hops_iter)?, next_account_info(hops_iter)?));
+    }
+
+    msg!("Routing through {} booths.", booths.len());
+
+    // Every vault of the route and both user token accounts must be distinct, so no hop can
+    // pay into or out of an account another part of the route is also using
+    let mut token_accounts = vec![user_source_account, user_destination_account];
+    token_accounts.extend(booths.iter().flat_map(|booth| [booth.vault_a, booth.vault_b]));
+    assert_unique(&token_accounts, "The route's token accounts must all differ")?;
+
+    // One token program moves the tokens of every hop
+    for booth in &booths {
+        assert_token_program(token_program, &[booth.mint_a, booth.mint_b])?;
+    }
+
+    // Price every hop up front so a bad route fails before any funds move. Each hop is fed
+    // what the previous one pays out, before the next vault's transfer fee.
+    let user_source = unpack_token_account(user_source_account)?;
+    let mut legs = Vec::with_capacity(booths.len());
+    let mut mint = user_source.mint;
+    let mut amount = amount_in;
+    let mut amount_out = 0;
+    for (booth, (user_volume_acct, access_acct)) in booths.iter().zip(user_accts.iter()) {
+        let leg = quote(program_id, booth, &mint, amount)?;
+        check_access(program_id, booth, &leg, user_account, access_acct)?;
+        let mut leg = leg.into_mut(program_id, booth)?;
+        record_volume(program_id, booth, &mut leg, user_account, user_volume_acct, system_program)?;
+        mint = leg.output_mint();
+        amount = leg.payout;
+        amount_out = leg.amount_out;
+        legs.push(leg);
+    }
+
+    let user_destination = unpack_token_account(user_destination_account)?;
+    assert_with_msg(
+        user_destination.mint == mint,
+        ExchangeBoothError::InvalidAccountData.into(),
+        "The user destination token account does not match the last hop's output token",
+    )?;
+
+    assert_with_msg(
+        amount_out >= min_amount_out,
+        ExchangeBoothError::SlippageExceeded.into(),
+        "Route would return less than the minimum amount out",
+    )?;
+
+    // The user funds the first booth. Each booth then pays straight into the next booth's
+    // input vault, and the last one pays the user.
+    token::transfer_checked(
+        token_program,
+        user_source_account,
+        legs[0].input_mint_account(&booths[0]),
+        legs[0].input_vault,
+        user_account,
+        amount_in,
+        &[],
+    )?;
+
+    for i in 0..legs.len() {
+        let destination = match legs.get(i + 1) {
+            Some(next_leg) => next_leg.input_vault,
+            None => user_destination_account,
+        };
+        pay_out(program_id, &booths[i], &legs[i], destination, token_program, legs[i].payout)?;
+        record_fee(&mut legs[i], &protocol_config)?;
+        legs[i].event(booths[i].exchange_booth.key, user_account.key, None).emit();
+    }
+
+    msg!("Routed {} tokens into {} tokens.", amount_in, amount_out);
+
+    Ok(())
+}
@@ -0,0 +1,93 @@
+use solana_program::{
+    account_info::{AccountInfo, next_account_info},
+    entrypoint::ProgramResult, msg,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvar::{rent::Rent, Sysvar},
+};
+
+use crate::{
+    account::create_pda_account,
+    state::{AdminMultisig, ExchangeBooth, MAX_MULTISIG_SIGNERS},
+    validation::{assert_owned_by, assert_pda, assert_signer, assert_with_msg},
+};
+
+pub fn process(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    signers: Vec<Pubkey>,
+    threshold: u8,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+    let admin = next_account_info(accounts_iter)?;
+    let exchange_booth_acct = next_account_info(accounts_iter)?;
+    let multisig_acct = next_account_info(accounts_iter)?;
+    let payer = next_account_info(accounts_iter)?;
+    let system_program = next_account_info(accounts_iter)?;
+
+    assert_with_msg(
+        !signers.is_empty() && signers.len() <= MAX_MULTISIG_SIGNERS,
+        ProgramError::InvalidArgument,
+        "A multisig needs between 1 and 11 signers.",
+    )?;
+
+    assert_with_msg(
+        threshold >= 1 && threshold as usize <= signers.len(),
+        ProgramError::InvalidArgument,
+        "Threshold must be between 1 and the number of signers.",
+    )?;
+
+    for (i, signer) in signers.iter().enumerate() {
+        assert_with_msg(
+            !signers[..i].contains(signer),
+            ProgramError::InvalidArgument,
+            "Multisig signers must be unique.",
+        )?;
+    }
+
+    assert_owned_by(
+        exchange_booth_acct,
+        program_id,
+        "'Exchange Booth' Account is not owned by the Exchange Booth program.",
+    )?;
+
+    let mut exchange_booth = ExchangeBooth::load_mut(exchange_booth_acct, program_id)?;
+
+    AdminMultisig::authorize(
+        program_id,
+        exchange_booth_acct.key,
+        &exchange_booth.admin,
+        admin,
+        accounts_iter.as_slice(),
+    )?;
+
+    let bump = assert_pda(
+        multisig_acct,
+        AdminMultisig::find_address(exchange_booth_acct.key, program_id),
+        "Key returned from find_program_address was not equal to the key passed as the 'multisig' Account.",
+    )?;
+
+    if multisig_acct.data_is_empty() {
+        assert_signer(payer, "Fourth account passed for 'Payer' is not a signer as is required.")?;
+
+        create_pda_account(
+            payer,
+            multisig_acct,
+            Rent::get()?.minimum_balance(AdminMultisig::LEN),
+            AdminMultisig::LEN,
+            program_id,
+            system_program,
+            &[b"multisig", exchange_booth_acct.key.as_ref(), &[bump]],
+        )?;
+    }
+
+    AdminMultisig { threshold, signers }.save(multisig_acct)?;
+
+    // from now on the booth is administered through the multisig PDA
+    exchange_booth.admin = *multisig_acct.key;
+    exchange_booth.pending_admin = Pubkey::default();
+
+    msg!("Exchange Booth Admin is now multisig '{}' ({} required).", multisig_acct.key, threshold);
+
+    Ok(())
+}
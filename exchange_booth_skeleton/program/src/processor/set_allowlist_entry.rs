@@ -0,0 +1,95 @@
+use solana_program::{
+    account_info::{AccountInfo, next_account_info},
+    entrypoint::ProgramResult, msg,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvar::{rent::Rent, Sysvar},
+};
+
+use crate::{
+    account::create_pda_account,
+    state::{AdminMultisig, AllowlistEntry, ExchangeBooth},
+    validation::{assert_owned_by, assert_pda, assert_signer},
+};
+
+pub fn process(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    user: Pubkey,
+    allowed: bool,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+    let admin = next_account_info(accounts_iter)?;
+    let exchange_booth_acct = next_account_info(accounts_iter)?;
+    let allowlist_entry_acct = next_account_info(accounts_iter)?;
+    let payer = next_account_info(accounts_iter)?;
+    let system_program = next_account_info(accounts_iter)?;
+
+    assert_owned_by(
+        exchange_booth_acct,
+        program_id,
+        "'Exchange Booth' Account is not owned by the Exchange Booth program.",
+    )?;
+
+    let exchange_booth = ExchangeBooth::load(exchange_booth_acct, program_id)?;
+
+    AdminMultisig::authorize(
+        program_id,
+        exchange_booth_acct.key,
+        &exchange_booth.admin,
+        admin,
+        accounts_iter.as_slice(),
+    )?;
+
+    let bump = assert_pda(
+        allowlist_entry_acct,
+        AllowlistEntry::find_address(exchange_booth_acct.key, &user, program_id),
+        "Key returned from find_program_address was not equal to the key passed as the 'allowlist_entry' Account.",
+    )?;
+
+    if allowed {
+        if allowlist_entry_acct.data_is_empty() {
+            assert_signer(
+                payer,
+                "Fourth account passed for 'Payer' is not a signer as is required.",
+            )?;
+
+            create_pda_account(
+                payer,
+                allowlist_entry_acct,
+                Rent::get()?.minimum_balance(AllowlistEntry::LEN),
+                AllowlistEntry::LEN,
+                program_id,
+                system_program,
+                &[b"allowlist", exchange_booth_acct.key.as_ref(), user.as_ref(), &[bump]],
+            )?;
+        }
+
+        AllowlistEntry {
+            exchange_booth: *exchange_booth_acct.key,
+            user,
+        }
+        .save(allowlist_entry_acct)?;
+
+        msg!("Allowed '{}' to exchange.", user);
+    } else {
+        assert_owned_by(
+            allowlist_entry_acct,
+            program_id,
+            "'Allowlist Entry' Account is not owned by the Exchange Booth program.",
+        )?;
+
+        // close the entry and refund its rent
+        let entry_lamports = allowlist_entry_acct.lamports();
+        **allowlist_entry_acct.lamports.borrow_mut() = 0;
+        **payer.lamports.borrow_mut() = payer
+            .lamports()
+            .checked_add(entry_lamports)
+            .ok_or(ProgramError::InvalidArgument)?;
+        allowlist_entry_acct.data.borrow_mut().fill(0);
+
+        msg!("Removed '{}' from the allowlist.", user);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,49 @@
+use solana_program::{
+    account_info::{AccountInfo, next_account_info},
+    entrypoint::ProgramResult, msg,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+};
+
+use crate::{
+    state::{AdminMultisig, ExchangeBooth, PAUSE_ALL},
+    validation::{assert_owned_by, assert_with_msg},
+};
+
+pub fn process(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    pause_flags: u8,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+    let admin = next_account_info(accounts_iter)?;
+    let exchange_booth_acct = next_account_info(accounts_iter)?;
+
+    assert_with_msg(
+        pause_flags & !PAUSE_ALL == 0,
+        ProgramError::InvalidArgument,
+        "Unknown pause flag bits set.",
+    )?;
+
+    assert_owned_by(
+        exchange_booth_acct,
+        program_id,
+        "'Exchange Booth' Account is not owned by the Exchange Booth program.",
+    )?;
+
+    let mut exchange_booth = ExchangeBooth::load_mut(exchange_booth_acct, program_id)?;
+
+    AdminMultisig::authorize(
+        program_id,
+        exchange_booth_acct.key,
+        &exchange_booth.admin,
+        admin,
+        accounts_iter.as_slice(),
+    )?;
+
+    exchange_booth.pause_flags = pause_flags;
+
+    msg!("Pause flags set to {:#05b}.", pause_flags);
+
+    Ok(())
+}
@@ -0,0 +1,105 @@
+use solana_program::{
+    account_info::{AccountInfo, next_account_info},
+    bpf_loader_upgradeable::{self, UpgradeableLoaderState},
+    entrypoint::ProgramResult, msg,
+    program_error::ProgramError,
+    pubkey::Pubkey,
+    sysvar::{rent::Rent, Sysvar},
+};
+
+use crate::{
+    account::create_pda_account,
+    error::ExchangeBoothError,
+    state::ProtocolConfig,
+    validation::{assert_key, assert_owned_by, assert_pda, assert_signer, assert_with_msg},
+};
+
+/// The upgrade authority recorded in the program's ProgramData account. The loader lays it out
+/// as a `u32` state tag (3 for ProgramData), the deployment slot and an optional authority.
+fn upgrade_authority(program_id: &Pubkey, program_data: &AccountInfo) -> Result<Option<Pubkey>, ProgramError> {
+    let (address, _) = Pubkey::find_program_address(&[program_id.as_ref()], &bpf_loader_upgradeable::id());
+    assert_key(program_data, &address, "Account passed for 'Program Data' is not this program's ProgramData.")?;
+    assert_owned_by(
+        program_data,
+        &bpf_loader_upgradeable::id(),
+        "'Program Data' Account is not owned by the upgradeable loader.",
+    )?;
+
+    let data = program_data.data.borrow();
+    let metadata = data
+        .get(..UpgradeableLoaderState::size_of_programdata_metadata())
+        .filter(|metadata| metadata[..4] == 3u32.to_le_bytes())
+        .ok_or(ExchangeBoothError::InvalidAccountData)?;
+    Ok(match metadata[12] {
+        0 => None,
+        _ => Some(Pubkey::new_from_array(metadata[13..45].try_into().unwrap())),
+    })
+}
+
+pub fn process(
+    program_id: &Pubkey,
+    accounts: &[AccountInfo],
+    treasury_owner: Pubkey,
+    protocol_fee_share_bps: u64,
+) -> ProgramResult {
+    let accounts_iter = &mut accounts.iter();
+    let authority = next_account_info(accounts_iter)?;
+    let protocol_config_acct = next_account_info(accounts_iter)?;
+    let system_program = next_account_info(accounts_iter)?;
+
+    assert_signer(
+        authority,
+        "First account passed for 'Authority' is not a signer as is required.",
+    )?;
+
+    assert_with_msg(
+        protocol_fee_share_bps <= 10_000,
+        ProgramError::InvalidArgument,
+        "protocol_fee_share_bps must be at most 10000.",
+    )?;
+
+    let bump = assert_pda(
+        protocol_config_acct,
+        ProtocolConfig::find_address(program_id),
+        "Key returned from find_program_address was not equal to the key passed as the 'protocol_config' Account.",
+    )?;
+
+    if protocol_config_acct.data_is_empty() {
+        // only the program's upgrade authority may create the config and name the first
+        // treasury owner, or anyone could claim the protocol fees of every booth
+        let program_data = next_account_info(accounts_iter)?;
+        assert_with_msg(
+            upgrade_authority(program_id, program_data)? == Some(*authority.key),
+            ExchangeBoothError::IncorrectAdmin.into(),
+            "Only the program's upgrade authority can create the protocol config.",
+        )?;
+
+        msg!("Protocol config does not exist yet. Creating it.");
+        create_pda_account(
+            authority,
+            protocol_config_acct,
+            Rent::get()?.minimum_balance(ProtocolConfig::LEN),
+            ProtocolConfig::LEN,
+            program_id,
+            system_program,
+            &[b"protocol_config", &[bump]],
+        )?;
+    } else {
+        let protocol_config = ProtocolConfig::load_or_default(protocol_config_acct, program_id)?;
+        assert_with_msg(
+            protocol_config.treasury_owner == *authority.key,
+            ExchangeBoothError::IncorrectAdmin.into(),
+            "Only the current treasury owner can change the protocol config.",
+        )?;
+    }
+
+    ProtocolConfig {
+        treasury_owner,
+        protocol_fee_share_bps,
+    }
+    .save(protocol_config_acct)?;
+
+    msg!("Protocol fee share set to {} bps.", protocol_fee_share_bps);
+
+    Ok(())
+}
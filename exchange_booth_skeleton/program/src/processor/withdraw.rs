@@ -1,28 +1,78 @@
 use solana_program::{
     account_info::{AccountInfo, next_account_info},
+    clock::Clock,
     entrypoint::ProgramResult, msg, 
     pubkey::Pubkey,
     program_error::ProgramError,
-    program::{invoke_signed, },
+    sysvar::Sysvar,
 };
 
 use crate::{
-    state::ExchangeBooth,
+    events::BoothEvent,
+    state::{AdminMultisig, ExchangeBooth, PAUSE_WITHDRAWALS},
+    token::{self, assert_token_program, unpack_token_account},
+    validation::{assert_owned_by, assert_pda, assert_unique, assert_with_msg},
 };
 
-use spl_token::{
-    id, instruction,
-};
+/// Moves `amount_to_withdraw` out of one of the booth's vaults, signed by the vault PDA. Protocol fees
+/// owed to the treasury stay in the vault. Shared by `Withdraw` and `ExecuteWithdrawal`.
+#[allow(clippy::too_many_arguments)]
+pub fn send_from_vault<'info>(
+    program_id: &Pubkey,
+    exchange_booth_acct: &AccountInfo<'info>,
+    exchange_booth: &ExchangeBooth,
+    vault: &AccountInfo<'info>,
+    mint: &AccountInfo<'info>,
+    destination: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+    amount_to_withdraw: u64,
+) -> ProgramResult {
+    // Protocol fees sit in the vaults until the treasury collects them and are not the admin's
+    let (seed, booth_mint, protocol_fees): (&[u8], Pubkey, u64) = if *vault.key == exchange_booth.vault_a {
+        (b"vault_a", exchange_booth.mint_a, exchange_booth.protocol_fees_a)
+    } else if *vault.key == exchange_booth.vault_b {
+        (b"vault_b", exchange_booth.mint_b, exchange_booth.protocol_fees_b)
+    } else {
+        msg!("Vault passed in is not a Vault of the Exchange Booth.");
+        return Err(ProgramError::InvalidArgument);
+    };
+
+    assert_with_msg(
+        *mint.key == booth_mint,
+        ProgramError::InvalidArgument,
+        "Mint passed in is not the mint of the Vault.",
+    )?;
+    assert_token_program(token_program, &[mint])?;
 
-use borsh::{BorshDeserialize, };
+    let vault_state = unpack_token_account(vault)?;
+    assert_with_msg(
+        amount_to_withdraw <= vault_state.amount.saturating_sub(protocol_fees),
+        ProgramError::InsufficientFunds,
+        "Withdrawal would dip into protocol fees owed to the treasury.",
+    )?;
 
-pub fn assert_with_msg(statement: bool, err: ProgramError, msg: &str) -> ProgramResult {
-    if !statement {
-        msg!(msg);
-        Err(err)
-    } else {
-        Ok(())
+    msg!("Basic checks passed. Calc'ing Vault bump seed.");
+
+    let bump = assert_pda(
+        vault,
+        Pubkey::find_program_address(&[seed, exchange_booth_acct.key.as_ref()], program_id),
+        "Vault key from Find PDA doesn't match passed in Vault addr.",
+    )?;
+
+    msg!("Got right Vault PDA. Invoking the CPI.");
+
+    token::transfer_checked(token_program, vault, mint, destination, vault, amount_to_withdraw,
+        &[&[seed, exchange_booth_acct.key.as_ref(), &[bump]]])?;
+
+    BoothEvent::Withdrawn {
+        exchange_booth: *exchange_booth_acct.key,
+        vault: *vault.key,
+        destination: *destination.key,
+        amount: amount_to_withdraw,
     }
+    .emit();
+
+    Ok(())
 }
 
 pub fn process(
@@ -42,51 +92,37 @@ pub fn process(
 
     msg!("Got all six accounts.");
 
-    assert_with_msg(
-        admin.is_signer,
-        ProgramError::MissingRequiredSignature,
-        "First account passed for 'Admin' is not a signer as is required.",
+    assert_unique(
+        &[admins_token_account, vault],
+        "The admin's token account can not be the Vault.",
     )?;
 
-    msg!("Admin is a signer. Getting the Exchange Booth and validating the Admin's Public Key.");
+    msg!("Getting the Exchange Booth and validating the Admin.");
 
-    let exchange_booth = ExchangeBooth::try_from_slice(&exchange_booth_acct.data.borrow())?;
-
-    assert_with_msg(
-        exchange_booth.admin == *admin.key,
-        ProgramError::InvalidArgument,
-        "First account passed for 'Admin' does not have a Public Key \"
-        matching the Admin known in the fifth argument 'Exchange Booth'.",
+    assert_owned_by(
+        exchange_booth_acct,
+        program_id,
+        "Fifth account passed for 'Exchange Booth' is not owned by the Exchange Booth program.",
     )?;
 
-    msg!("Basic checks passed. Creating transfer instruction.");
-
-    let transfer_ix = instruction::transfer(
-                token_program.key, // &id(),
-                vault.key,
-                admins_token_account.key,
-                &vault.key,
-                &[],
-                amount_to_withdraw)?;
-
-    msg!("Transfer instruction created. Calc'ing Vault bump seed.");
-
-    let (vault_key, bump) = Pubkey::find_program_address(
-        &[b"vault_b", exchange_booth_acct.key.as_ref()], program_id);
+    let mut exchange_booth = ExchangeBooth::load_mut(exchange_booth_acct, program_id)?;
+    exchange_booth.check_not_paused(PAUSE_WITHDRAWALS)?;
 
-    assert_with_msg(
-        vault_key == *vault.key,
-        ProgramError::InvalidArgument,
-        "Vault key from Find PDA doesn't match passed in Vault addr.",
+    // the admin either signs itself or is a multisig whose signers trail the accounts
+    AdminMultisig::authorize(
+        program_id,
+        exchange_booth_acct.key,
+        &exchange_booth.admin,
+        admin,
+        accounts_iter.as_slice(),
     )?;
 
-    msg!("Got right Vault PDA. Invoking the CPI.");
+    let from_vault_a = *vault.key == exchange_booth.vault_a;
+    exchange_booth.record_withdrawal(from_vault_a, amount_to_withdraw, Clock::get()?.slot)?;
 
-    invoke_signed(&transfer_ix,
-        &[vault.clone(), admins_token_account.clone(), token_program.clone()], 
-        &[&[b"vault_b", exchange_booth_acct.key.as_ref(), &[bump]]])?;
+    send_from_vault(program_id, exchange_booth_acct, &exchange_booth, vault, mint, admins_token_account, token_program, amount_to_withdraw)?;
 
-    msg!("CPI invoked succesffully! Withdraw complete!!");
+    msg!("Withdraw complete.");
 
     Ok(())
 }
@@ -1,39 +1,654 @@
 use borsh::{BorshDeserialize, BorshSerialize};
+use bytemuck::{Pod, Zeroable};
 use solana_program::{
     account_info::AccountInfo, entrypoint::ProgramResult, program_error::ProgramError,
     pubkey::Pubkey,
 };
-use std::mem::size_of;
+use std::{
+    cell::{Ref, RefMut},
+    mem::size_of,
+};
 
-#[derive(BorshSerialize, BorshDeserialize, Debug, Clone)]
+use crate::error::ExchangeBoothError;
+
+/// Every account the program owns starts with a discriminator naming its type and the
+/// version of the layout it was written with, followed by the Borsh encoded state.
+pub const ACCOUNT_HEADER_LEN: usize = 2;
+
+fn check_account_header(data: &[u8], discriminator: u8, version: u8) -> ProgramResult {
+    if data.len() < ACCOUNT_HEADER_LEN || data[0] == 0 {
+        return Err(ExchangeBoothError::AccountNotInitialized.into());
+    }
+    if data[0] != discriminator {
+        return Err(ExchangeBoothError::InvalidAccountData.into());
+    }
+    if data[1] != version {
+        return Err(ExchangeBoothError::AccountNeedsMigration.into());
+    }
+    Ok(())
+}
+
+/// Account types stored behind the common header.
+pub trait AccountState: BorshSerialize + BorshDeserialize {
+    const DISCRIMINATOR: u8;
+    const VERSION: u8;
+
+    /// Checks the header and decodes the state behind it. Any unused tail of a fixed
+    /// capacity account is ignored.
+    fn unpack_account(data: &[u8]) -> Result<Self, ProgramError> {
+        check_account_header(data, Self::DISCRIMINATOR, Self::VERSION)?;
+        Ok(Self::deserialize(&mut &data[ACCOUNT_HEADER_LEN..])?)
+    }
+
+    fn pack_account(&self, data: &mut [u8]) -> ProgramResult {
+        if data.len() < ACCOUNT_HEADER_LEN {
+            return Err(ProgramError::AccountDataTooSmall);
+        }
+        data[0] = Self::DISCRIMINATOR;
+        data[1] = Self::VERSION;
+        Ok(self.serialize(&mut &mut data[ACCOUNT_HEADER_LEN..])?)
+    }
+}
+
+/// `ExchangeBooth::pause_flags` bits.
+pub const PAUSE_DEPOSITS: u8 = 1 << 0;
+pub const PAUSE_WITHDRAWALS: u8 = 1 << 1;
+pub const PAUSE_EXCHANGES: u8 = 1 << 2;
+pub const PAUSE_ALL: u8 = PAUSE_DEPOSITS | PAUSE_WITHDRAWALS | PAUSE_EXCHANGES;
+
+/// Booth state, read and written in place through `load` and `load_mut` rather than decoded.
+/// The struct is packed, so the account is exactly these bytes (offsets include the header):
+///
+/// | offset | field                   | offset | field                   |
+/// |--------|-------------------------|--------|-------------------------|
+/// | 0      | discriminator (u8)      | 307    | protocol_fees_b         |
+/// | 1      | version (u8)            | 315    | referral_share_bps      |
+/// | 2      | initialized (u8)        | 323    | pause_flags (u8)        |
+/// | 3      | admin                   | 324    | per_user_epoch_cap      |
+/// | 35     | pending_admin           | 332    | epoch_cap               |
+/// | 67     | mint_a                  | 340    | epoch                   |
+/// | 99     | mint_b                  | 348    | epoch_volume            |
+/// | 131    | vault_a                 | 356    | access_gated (u8)       |
+/// | 163    | vault_b                 | 357    | gating_mint             |
+/// | 195    | oracle                  | 389    | gating_min_balance      |
+/// | 227    | spread_bps              | 397    | withdrawal_limit        |
+/// | 235    | flash_loan_fee_bps      | 405    | withdrawal_window_slots |
+/// | 243    | flash_loan_vault        | 413    | withdrawal_window_start |
+/// | 275    | flash_loan_due          | 421    | withdrawn_a             |
+/// | 283    | fees_a                  | 429    | withdrawn_b             |
+/// | 291    | fees_b                  | 437    | end                     |
+/// | 299    | protocol_fees_a         |        |                         |
+///
+/// Keys are 32 bytes, integers little endian u64 unless noted, and flags are 0 or 1. This
+/// is the same layout version 1 booths were written with through Borsh.
+#[repr(C, packed)]
+#[derive(Debug, Clone, Copy, Pod, Zeroable)]
 pub struct ExchangeBooth {
-    pub initialized: bool,
+    pub initialized: u8,
     pub admin: Pubkey,
+    pub pending_admin: Pubkey, // proposed by `admin`, default if none
     pub mint_a: Pubkey,
     pub mint_b: Pubkey,
     pub vault_a: Pubkey, // token account controlled by PDA
     pub vault_b: Pubkey, // token account controlled by PDA
     pub oracle: Pubkey,
-    // pub spread_bps: u64, // not using right away
+    pub spread_bps: u64, // taken out of the output side of every exchange
+    pub flash_loan_fee_bps: u64,
+    pub flash_loan_vault: Pubkey, // vault with an outstanding flash loan, default if none
+    pub flash_loan_due: u64, // principal plus fee owed back to `flash_loan_vault`
+    pub fees_a: u64, // admin share of the fees collected in vault_a
+    pub fees_b: u64,
+    pub protocol_fees_a: u64, // treasury share held in vault_a until collected
+    pub protocol_fees_b: u64,
+    pub referral_share_bps: u64, // share of the fee paid to a referrer, if the exchange names one
+    pub pause_flags: u8, // PAUSE_* bits
+    pub per_user_epoch_cap: u64, // max volume per user per epoch in token A units, 0 for none
+    pub epoch_cap: u64, // max volume of the whole booth per epoch in token A units, 0 for none
+    pub epoch: u64, // epoch `epoch_volume` was counted in
+    pub epoch_volume: u64,
+    pub access_gated: u8, // Exchange needs an allowlist entry or enough of `gating_mint`
+    pub gating_mint: Pubkey, // default if only the allowlist grants access
+    pub gating_min_balance: u64,
+    pub withdrawal_limit: u64, // max Withdraw per vault per window, 0 to queue every withdrawal
+    pub withdrawal_window_slots: u64,
+    pub withdrawal_window_start: u64, // slot the current window started in
+    pub withdrawn_a: u64, // withdrawn from vault_a in the current window
+    pub withdrawn_b: u64,
 }
 
 impl ExchangeBooth {
-    pub fn load_unchecked(ai: &AccountInfo) -> Result<Self, ProgramError> {
-        Ok(Self::try_from_slice(&ai.data.borrow())?)
+    pub const DISCRIMINATOR: u8 = 1;
+    pub const VERSION: u8 = 1;
+
+    pub const LEN: usize = ACCOUNT_HEADER_LEN + size_of::<Self>();
+
+    /// Size of a booth from before booths had a header: the initialized flag and the admin,
+    /// mints, vaults and oracle, Borsh encoded.
+    pub const UNVERSIONED_LEN: usize = 1 + 6 * size_of::<Pubkey>();
+
+    /// Vault PDA for `vault_seed`, either `b"vault_a"` or `b"vault_b"`.
+    pub fn find_vault_address(exchange_booth: &Pubkey, vault_seed: &[u8], program_id: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[vault_seed, exchange_booth.as_ref()], program_id)
     }
 
-    fn validate(&self) -> ProgramResult {
-        // none
+    fn check_account(ai: &AccountInfo, program_id: &Pubkey) -> ProgramResult {
+        if ai.owner != program_id {
+            return Err(ExchangeBoothError::InvalidAccountOwner.into());
+        }
+        if ai.data_len() == Self::UNVERSIONED_LEN {
+            return Err(ExchangeBoothError::AccountNeedsMigration.into());
+        }
+        if ai.data_len() != Self::LEN {
+            return Err(ExchangeBoothError::InvalidAccountData.into());
+        }
         Ok(())
     }
 
+    /// Takes the zeroed account `InitializeExchangeBooth` is about to set up, refusing one that
+    /// already holds a booth, and stamps the header on it.
+    pub fn load_uninitialized<'a>(
+        ai: &'a AccountInfo,
+        program_id: &Pubkey,
+    ) -> Result<RefMut<'a, Self>, ProgramError> {
+        Self::check_account(ai, program_id)?;
+        let mut data = ai.try_borrow_mut_data()?;
+        if data[0] != 0 || data[ACCOUNT_HEADER_LEN] != 0 {
+            return Err(ExchangeBoothError::AccountAlreadyInitialized.into());
+        }
+        data[0] = Self::DISCRIMINATOR;
+        data[1] = Self::VERSION;
+        Ok(RefMut::map(data, |data| bytemuck::from_bytes_mut(&mut data[ACCOUNT_HEADER_LEN..])))
+    }
+
+    fn check_data(data: &[u8]) -> ProgramResult {
+        check_account_header(data, Self::DISCRIMINATOR, Self::VERSION)?;
+        if data[ACCOUNT_HEADER_LEN] != 1 {
+            return Err(ExchangeBoothError::AccountNotInitialized.into());
+        }
+        Ok(())
+    }
+
+    /// Borrows the booth straight out of the account data. The borrow has to be dropped
+    /// before anything else touches the account's data.
+    pub fn load<'a>(ai: &'a AccountInfo, program_id: &Pubkey) -> Result<Ref<'a, Self>, ProgramError> {
+        Self::check_account(ai, program_id)?;
+        let data = ai.try_borrow_data()?;
+        Self::check_data(&data)?;
+        Ok(Ref::map(data, |data| bytemuck::from_bytes(&data[ACCOUNT_HEADER_LEN..])))
+    }
+
+    /// Like `load`, but writes go straight to the account data.
+    pub fn load_mut<'a>(
+        ai: &'a AccountInfo,
+        program_id: &Pubkey,
+    ) -> Result<RefMut<'a, Self>, ProgramError> {
+        Self::check_account(ai, program_id)?;
+        let data = ai.try_borrow_mut_data()?;
+        Self::check_data(&data)?;
+        Ok(RefMut::map(data, |data| bytemuck::from_bytes_mut(&mut data[ACCOUNT_HEADER_LEN..])))
+    }
+
+    pub fn check_not_paused(&self, flag: u8) -> ProgramResult {
+        if self.pause_flags & flag != 0 {
+            return Err(ExchangeBoothError::Paused.into());
+        }
+        Ok(())
+    }
+
+    /// Counts a direct withdrawal against the per-vault limit of the current window, starting
+    /// a new window once `withdrawal_window_slots` have passed. Anything above the limit has
+    /// to go through a queued withdrawal, and with a limit of 0 everything does.
+    pub fn record_withdrawal(&mut self, from_vault_a: bool, amount: u64, slot: u64) -> ProgramResult {
+        if self.withdrawal_limit == 0 {
+            return Err(ExchangeBoothError::WithdrawalLimitExceeded.into());
+        }
+
+        if slot >= self.withdrawal_window_start.saturating_add(self.withdrawal_window_slots) {
+            self.withdrawal_window_start = slot;
+            self.withdrawn_a = 0;
+            self.withdrawn_b = 0;
+        }
+
+        let withdrawn = if from_vault_a { self.withdrawn_a } else { self.withdrawn_b };
+        let withdrawn = withdrawn.saturating_add(amount);
+        if withdrawn > self.withdrawal_limit {
+            return Err(ExchangeBoothError::WithdrawalLimitExceeded.into());
+        }
+        if from_vault_a {
+            self.withdrawn_a = withdrawn;
+        } else {
+            self.withdrawn_b = withdrawn;
+        }
+        Ok(())
+    }
+}
+
+/// Minimum number of slots between queueing a config change and applying it, so users get
+/// to see a change coming (about an hour at 400ms slots).
+pub const MIN_CONFIG_CHANGE_DELAY_SLOTS: u64 = 9_000;
+
+/// A booth parameter change that has to go through the timelocked queue.
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+pub enum ConfigChange {
+    SpreadBps(u64),
+    FlashLoanFeeBps(u64),
+    ReferralShareBps(u64),
+    Oracle(Pubkey),
+    PerUserEpochCap(u64),
+    EpochCap(u64),
+    AccessGate {
+        gated: bool,
+        gating_mint: Pubkey,
+        gating_min_balance: u64,
+    },
+    WithdrawalLimit {
+        withdrawal_limit: u64,
+        window_slots: u64,
+    },
+}
+
+impl ConfigChange {
+    pub fn validate(&self) -> ProgramResult {
+        match self {
+            ConfigChange::SpreadBps(bps)
+            | ConfigChange::FlashLoanFeeBps(bps)
+            | ConfigChange::ReferralShareBps(bps) => {
+                if *bps > 10_000 {
+                    return Err(ProgramError::InvalidArgument);
+                }
+            }
+            ConfigChange::Oracle(_)
+            | ConfigChange::PerUserEpochCap(_)
+            | ConfigChange::EpochCap(_)
+            | ConfigChange::AccessGate { .. } => {}
+            ConfigChange::WithdrawalLimit { withdrawal_limit, window_slots } => {
+                if *withdrawal_limit > 0 && *window_slots == 0 {
+                    return Err(ProgramError::InvalidArgument);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    pub fn apply(&self, exchange_booth: &mut ExchangeBooth) -> ProgramResult {
+        self.validate()?;
+        match self {
+            ConfigChange::SpreadBps(bps) => exchange_booth.spread_bps = *bps,
+            ConfigChange::FlashLoanFeeBps(bps) => exchange_booth.flash_loan_fee_bps = *bps,
+            ConfigChange::ReferralShareBps(bps) => exchange_booth.referral_share_bps = *bps,
+            ConfigChange::Oracle(oracle) => exchange_booth.oracle = *oracle,
+            ConfigChange::PerUserEpochCap(cap) => exchange_booth.per_user_epoch_cap = *cap,
+            ConfigChange::EpochCap(cap) => exchange_booth.epoch_cap = *cap,
+            ConfigChange::AccessGate { gated, gating_mint, gating_min_balance } => {
+                exchange_booth.access_gated = *gated as u8;
+                exchange_booth.gating_mint = *gating_mint;
+                exchange_booth.gating_min_balance = *gating_min_balance;
+            }
+            ConfigChange::WithdrawalLimit { withdrawal_limit, window_slots } => {
+                exchange_booth.withdrawal_limit = *withdrawal_limit;
+                exchange_booth.withdrawal_window_slots = *window_slots;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A queued `ConfigChange`, stored in the `[b"config_change", exchange_booth, change_id]` PDA
+/// until it is applied or cancelled.
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone)]
+pub struct QueuedConfigChange {
+    pub exchange_booth: Pubkey,
+    pub payer: Pubkey, // gets the rent back when the change is applied or cancelled
+    pub earliest_apply_slot: u64,
+    pub change: ConfigChange,
+}
+
+impl AccountState for QueuedConfigChange {
+    const DISCRIMINATOR: u8 = 5;
+    const VERSION: u8 = 1;
+}
+
+impl QueuedConfigChange {
+    /// Leaves room for a `ConfigChange` payload of up to 64 bytes.
+    pub const LEN: usize = ACCOUNT_HEADER_LEN + 2 * size_of::<Pubkey>() + 8 + 1 + 64;
+
+    pub fn find_address(exchange_booth: &Pubkey, change_id: u64, program_id: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[b"config_change", exchange_booth.as_ref(), &change_id.to_le_bytes()],
+            program_id,
+        )
+    }
+
+    pub fn load(ai: &AccountInfo) -> Result<Self, ProgramError> {
+        Self::unpack_account(&ai.data.borrow())
+    }
+
+    pub fn save(&self, ai: &AccountInfo) -> ProgramResult {
+        self.pack_account(&mut ai.data.borrow_mut())
+    }
+}
+
+/// Minimum number of slots a queued withdrawal has to wait before it can be executed.
+pub const MIN_WITHDRAWAL_DELAY_SLOTS: u64 = 9_000;
+
+/// A withdrawal above the booth's withdrawal limit, stored in the
+/// `[b"withdrawal", exchange_booth, withdrawal_id]` PDA until it is executed or cancelled.
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone)]
+pub struct QueuedWithdrawal {
+    pub exchange_booth: Pubkey,
+    pub payer: Pubkey, // gets the rent back when the withdrawal is executed or cancelled
+    pub vault: Pubkey,
+    pub destination: Pubkey, // token account the tokens go to, fixed when queued
+    pub amount: u64,
+    pub earliest_execute_slot: u64,
+}
+
+impl AccountState for QueuedWithdrawal {
+    const DISCRIMINATOR: u8 = 8;
+    const VERSION: u8 = 1;
+}
+
+impl QueuedWithdrawal {
+    pub const LEN: usize = ACCOUNT_HEADER_LEN + 4 * size_of::<Pubkey>() + 8 + 8;
+
+    pub fn find_address(exchange_booth: &Pubkey, withdrawal_id: u64, program_id: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[b"withdrawal", exchange_booth.as_ref(), &withdrawal_id.to_le_bytes()],
+            program_id,
+        )
+    }
+
+    pub fn load(ai: &AccountInfo) -> Result<Self, ProgramError> {
+        Self::unpack_account(&ai.data.borrow())
+    }
+
+    pub fn save(&self, ai: &AccountInfo) -> ProgramResult {
+        self.pack_account(&mut ai.data.borrow_mut())
+    }
+}
+
+/// A user's traded volume on one booth, stored in the `[b"user_volume", exchange_booth, user]`
+/// PDA.
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Default)]
+pub struct UserVolume {
+    pub epoch: u64,
+    pub volume: u64, // in token A units
+}
+
+impl AccountState for UserVolume {
+    const DISCRIMINATOR: u8 = 6;
+    const VERSION: u8 = 1;
+}
+
+impl UserVolume {
+    pub const LEN: usize = ACCOUNT_HEADER_LEN + 16;
+
+    pub fn find_address(exchange_booth: &Pubkey, user: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[b"user_volume", exchange_booth.as_ref(), user.as_ref()],
+            program_id,
+        )
+    }
+
+    pub fn load(ai: &AccountInfo) -> Result<Self, ProgramError> {
+        Self::unpack_account(&ai.data.borrow())
+    }
+
+    pub fn save(&self, ai: &AccountInfo) -> ProgramResult {
+        self.pack_account(&mut ai.data.borrow_mut())
+    }
+}
+
+/// Marks `user` as allowed to exchange on an access gated booth. Stored in the
+/// `[b"allowlist", exchange_booth, user]` PDA; closing it revokes access.
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Default)]
+pub struct AllowlistEntry {
+    pub exchange_booth: Pubkey,
+    pub user: Pubkey,
+}
+
+impl AccountState for AllowlistEntry {
+    const DISCRIMINATOR: u8 = 7;
+    const VERSION: u8 = 1;
+}
+
+impl AllowlistEntry {
+    pub const LEN: usize = ACCOUNT_HEADER_LEN + 2 * size_of::<Pubkey>();
+
+    pub fn find_address(exchange_booth: &Pubkey, user: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(
+            &[b"allowlist", exchange_booth.as_ref(), user.as_ref()],
+            program_id,
+        )
+    }
+
+    pub fn load(ai: &AccountInfo) -> Result<Self, ProgramError> {
+        Self::unpack_account(&ai.data.borrow())
+    }
+
+    pub fn save(&self, ai: &AccountInfo) -> ProgramResult {
+        self.pack_account(&mut ai.data.borrow_mut())
+    }
+}
+
+/// Maximum number of signers in an admin multisig.
+pub const MAX_MULTISIG_SIGNERS: usize = 11;
+
+/// M-of-N signer set that stands in for a booth admin. It lives in the
+/// `[b"multisig", exchange_booth]` PDA and that address is stored as the booth's admin.
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Default)]
+pub struct AdminMultisig {
+    pub threshold: u8,
+    pub signers: Vec<Pubkey>,
+}
+
+impl AccountState for AdminMultisig {
+    const DISCRIMINATOR: u8 = 4;
+    const VERSION: u8 = 1;
+}
+
+impl AdminMultisig {
+    pub const LEN: usize = ACCOUNT_HEADER_LEN + 1 + 4 + MAX_MULTISIG_SIGNERS * size_of::<Pubkey>();
+
+    pub fn find_address(exchange_booth: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[b"multisig", exchange_booth.as_ref()], program_id)
+    }
+
+    pub fn load(ai: &AccountInfo) -> Result<Self, ProgramError> {
+        Self::unpack_account(&ai.data.borrow())
+    }
+
+    pub fn save(&self, ai: &AccountInfo) -> ProgramResult {
+        self.pack_account(&mut ai.data.borrow_mut())
+    }
+
+    /// Checks that `authority` may act as `expected`: either it is `expected` and signed, or
+    /// `expected` is the booth's multisig PDA and at least `threshold` of its signers are
+    /// among `signers`.
+    pub fn authorize(
+        program_id: &Pubkey,
+        exchange_booth: &Pubkey,
+        expected: &Pubkey,
+        authority: &AccountInfo,
+        signers: &[AccountInfo],
+    ) -> ProgramResult {
+        if authority.key != expected {
+            return Err(ExchangeBoothError::IncorrectAdmin.into());
+        }
+        if authority.is_signer {
+            return Ok(());
+        }
+
+        let (multisig_key, _) = Self::find_address(exchange_booth, program_id);
+        if *authority.key != multisig_key || authority.owner != program_id {
+            return Err(ExchangeBoothError::MissingRequiredSignature.into());
+        }
+
+        let multisig = Self::load(authority)?;
+        let signed = multisig
+            .signers
+            .iter()
+            .filter(|key| signers.iter().any(|ai| ai.is_signer && ai.key == *key))
+            .count();
+        if signed < multisig.threshold as usize {
+            return Err(ExchangeBoothError::NotEnoughSigners.into());
+        }
+        Ok(())
+    }
+}
+
+/// Exchange rate published by the oracle, an authorized echo buffer. It lives right after the
+/// buffer's 11 byte header: one whole token A is worth `price / 10^decimals` whole token B.
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Copy, PartialEq)]
+pub struct OraclePrice {
+    pub price: u64,
+    pub decimals: u8,
+}
+
+impl OraclePrice {
+    pub const OFFSET: usize = 11;
+
+    pub fn load(ai: &AccountInfo) -> Result<Self, ProgramError> {
+        let data = ai.data.borrow();
+        if data.len() < Self::OFFSET {
+            return Err(ExchangeBoothError::InvalidAccountData.into());
+        }
+        let oracle_price = Self::deserialize(&mut &data[Self::OFFSET..])?;
+        if oracle_price.price == 0 {
+            return Err(ExchangeBoothError::InvalidAccountData.into());
+        }
+        Ok(oracle_price)
+    }
+}
+
+/// Global protocol settings, stored in the `[b"protocol_config"]` PDA.
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Default)]
+pub struct ProtocolConfig {
+    pub treasury_owner: Pubkey,
+    pub protocol_fee_share_bps: u64, // share of every booth fee that goes to the treasury
+}
+
+impl AccountState for ProtocolConfig {
+    const DISCRIMINATOR: u8 = 2;
+    const VERSION: u8 = 1;
+}
+
+impl ProtocolConfig {
+    pub const LEN: usize = ACCOUNT_HEADER_LEN + size_of::<Pubkey>() + 8;
+
+    pub fn find_address(program_id: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[b"protocol_config"], program_id)
+    }
+
+    /// Loads the config, treating a not yet created config as a zero protocol share.
+    pub fn load_or_default(ai: &AccountInfo, program_id: &Pubkey) -> Result<Self, ProgramError> {
+        if *ai.key != Self::find_address(program_id).0 {
+            return Err(ExchangeBoothError::InvalidProgramAddress.into());
+        }
+        if ai.data_is_empty() {
+            return Ok(Self::default());
+        }
+        if ai.owner != program_id {
+            return Err(ExchangeBoothError::InvalidAccountOwner.into());
+        }
+        Self::unpack_account(&ai.data.borrow())
+    }
+
+    pub fn save(&self, ai: &AccountInfo) -> ProgramResult {
+        self.pack_account(&mut ai.data.borrow_mut())
+    }
+
+    /// Splits a fee into `(booth_share, protocol_share)`.
+    pub fn split_fee(&self, fee: u64) -> (u64, u64) {
+        let protocol_share = (fee as u128 * self.protocol_fee_share_bps.min(10_000) as u128 / 10_000) as u64;
+        (fee - protocol_share, protocol_share)
+    }
+}
+
+/// Every booth trading one mint pair, stored in the `[b"registry", mint_a, mint_b]` PDA with
+/// the mints in ascending order, so a pair has one registry whichever mint its booths call
+/// `mint_a`. The account is sized to its list and grows and shrinks by one key as booths come
+/// and go, so there is no limit on the number of booths.
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Default)]
+pub struct BoothRegistry {
+    pub mint_a: Pubkey, // the lower of the pair's mints
+    pub mint_b: Pubkey,
+    pub booths: Vec<Pubkey>, // in registration order
+}
+
+impl AccountState for BoothRegistry {
+    const DISCRIMINATOR: u8 = 3;
+    const VERSION: u8 = 1;
+}
+
+impl BoothRegistry {
+    /// Size of a registry holding `booths` booths.
+    pub const fn len(booths: usize) -> usize {
+        ACCOUNT_HEADER_LEN + 2 * size_of::<Pubkey>() + 4 + booths * size_of::<Pubkey>()
+    }
+
+    /// The pair's mints in the order the registry is derived and stored with.
+    pub fn sorted_pair(mint_a: &Pubkey, mint_b: &Pubkey) -> (Pubkey, Pubkey) {
+        if mint_a <= mint_b {
+            (*mint_a, *mint_b)
+        } else {
+            (*mint_b, *mint_a)
+        }
+    }
+
+    /// Registry of the booths trading `mint_a` and `mint_b`, in either order.
+    pub fn find_address(mint_a: &Pubkey, mint_b: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
+        let (mint_a, mint_b) = Self::sorted_pair(mint_a, mint_b);
+        Pubkey::find_program_address(&[b"registry", mint_a.as_ref(), mint_b.as_ref()], program_id)
+    }
+
+    pub fn load(ai: &AccountInfo) -> Result<Self, ProgramError> {
+        Self::unpack_account(&ai.data.borrow())
+    }
+
+    pub fn save(&self, ai: &AccountInfo) -> ProgramResult {
+        self.pack_account(&mut ai.data.borrow_mut())
+    }
+
+    pub fn remove(&mut self, exchange_booth: &Pubkey) -> ProgramResult {
+        let index = self
+            .booths
+            .iter()
+            .position(|booth| booth == exchange_booth)
+            .ok_or(ExchangeBoothError::BoothNotRegistered)?;
+        self.booths.remove(index);
+        Ok(())
+    }
+}
+
+/// Address of every pair's `BoothRegistry`, stored in the `[b"registry_index"]` PDA so clients
+/// can enumerate every booth of the program. A registry is listed when its pair's first booth
+/// creates it and stays listed after its last booth closes.
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Default)]
+pub struct RegistryIndex {
+    pub registries: Vec<Pubkey>, // in creation order
+}
+
+impl AccountState for RegistryIndex {
+    const DISCRIMINATOR: u8 = 9;
+    const VERSION: u8 = 1;
+}
+
+impl RegistryIndex {
+    /// Size of an index listing `registries` registries.
+    pub const fn len(registries: usize) -> usize {
+        ACCOUNT_HEADER_LEN + 4 + registries * size_of::<Pubkey>()
+    }
+
+    pub fn find_address(program_id: &Pubkey) -> (Pubkey, u8) {
+        Pubkey::find_program_address(&[b"registry_index"], program_id)
+    }
+
     pub fn load(ai: &AccountInfo) -> Result<Self, ProgramError> {
-        let account = Self::try_from_slice(&ai.data.borrow())?;
-        account.validate()?;
-        Ok(account)
+        Self::unpack_account(&ai.data.borrow())
     }
 
     pub fn save(&self, ai: &AccountInfo) -> ProgramResult {
-        Ok(self.serialize(&mut *ai.data.borrow_mut())?)
+        self.pack_account(&mut ai.data.borrow_mut())
     }
 }
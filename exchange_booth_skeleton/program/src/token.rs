@@ -0,0 +1,198 @@
+//! Reading mints and token accounts of either the legacy token program or Token-2022. Both
+//! share the base layouts, so everything is unpacked through Token-2022's extension aware
+//! state and works for legacy accounts too.
+
+use solana_program::{
+    account_info::AccountInfo, clock::Clock, entrypoint::ProgramResult, msg,
+    program::{invoke, invoke_signed}, program_error::ProgramError, pubkey::Pubkey,
+    sysvar::{rent::Rent, Sysvar},
+};
+use spl_token_2022::{
+    extension::{transfer_fee::TransferFeeConfig, BaseStateWithExtensions, ExtensionType, StateWithExtensions},
+    state::{Account, Mint},
+};
+
+use crate::{
+    account::create_pda_account,
+    error::ExchangeBoothError,
+    validation::{assert_pda, assert_with_msg},
+};
+
+/// The only mint extensions the booth accepts. Transfer fees are accounted for, and metadata,
+/// group and their pointers don't affect transfers. Anything else could freeze the vaults,
+/// hide their balances, move or burn their tokens or close the mint under the booth, so it
+/// is refused, including extensions added to Token-2022 later.
+const SUPPORTED_MINT_EXTENSIONS: [ExtensionType; 7] = [
+    ExtensionType::TransferFeeConfig,
+    ExtensionType::MetadataPointer,
+    ExtensionType::TokenMetadata,
+    ExtensionType::GroupPointer,
+    ExtensionType::TokenGroup,
+    ExtensionType::GroupMemberPointer,
+    ExtensionType::TokenGroupMember,
+];
+
+pub fn is_token_program(program_id: &Pubkey) -> bool {
+    *program_id == spl_token::id() || *program_id == spl_token_2022::id()
+}
+
+pub fn is_native_mint(mint: &Pubkey) -> bool {
+    *mint == spl_token::native_mint::id() || *mint == spl_token_2022::native_mint::id()
+}
+
+/// Fails with `IncorrectProgramId` unless `token_program` is one of the token programs and
+/// owns every mint or token account in `owned`.
+pub fn assert_token_program(token_program: &AccountInfo, owned: &[&AccountInfo]) -> ProgramResult {
+    assert_with_msg(is_token_program(token_program.key), ProgramError::IncorrectProgramId, "Invalid token program")?;
+    assert_with_msg(
+        owned.iter().all(|ai| ai.owner == token_program.key),
+        ProgramError::IncorrectProgramId,
+        "Token accounts are not owned by the token program passed in",
+    )
+}
+
+pub fn unpack_mint(ai: &AccountInfo) -> Result<Mint, ProgramError> {
+    let data = ai.data.borrow();
+    let mint = StateWithExtensions::<Mint>::unpack(&data).map_err(|_| ProgramError::InvalidAccountData)?;
+    Ok(mint.base)
+}
+
+pub fn unpack_token_account(ai: &AccountInfo) -> Result<Account, ProgramError> {
+    let data = ai.data.borrow();
+    let account = StateWithExtensions::<Account>::unpack(&data).map_err(|_| ProgramError::InvalidAccountData)?;
+    Ok(account.base)
+}
+
+/// Fails with `UnsupportedMintExtension` if the mint uses any extension outside of
+/// `SUPPORTED_MINT_EXTENSIONS`. Legacy mints have no extensions.
+pub fn check_mint_extensions(ai: &AccountInfo) -> ProgramResult {
+    let data = ai.data.borrow();
+    let mint = StateWithExtensions::<Mint>::unpack(&data).map_err(|_| ProgramError::InvalidAccountData)?;
+    for extension in mint.get_extension_types()? {
+        if !SUPPORTED_MINT_EXTENSIONS.contains(&extension) {
+            msg!("Mint {} uses the unsupported {:?} extension", ai.key, extension);
+            return Err(ExchangeBoothError::UnsupportedMintExtension.into());
+        }
+    }
+    Ok(())
+}
+
+/// Size of a token account for the mint, including the account extensions its mint
+/// extensions require, such as the withheld amount of a transfer fee.
+pub fn token_account_len(mint_ai: &AccountInfo) -> Result<usize, ProgramError> {
+    let data = mint_ai.data.borrow();
+    let mint = StateWithExtensions::<Mint>::unpack(&data).map_err(|_| ProgramError::InvalidAccountData)?;
+    let account_extensions = ExtensionType::get_required_init_account_extensions(&mint.get_extension_types()?);
+    ExtensionType::try_calculate_account_len::<Account>(&account_extensions)
+}
+
+/// The part of a transfer of `amount` the mint withholds as a Token-2022 transfer fee in the
+/// current epoch. The recipient gets `amount` less this.
+pub fn transfer_fee(mint_ai: &AccountInfo, amount: u64) -> Result<u64, ProgramError> {
+    let data = mint_ai.data.borrow();
+    let mint = StateWithExtensions::<Mint>::unpack(&data).map_err(|_| ProgramError::InvalidAccountData)?;
+    match mint.get_extension::<TransferFeeConfig>() {
+        Ok(config) => config
+            .calculate_epoch_fee(Clock::get()?.epoch, amount)
+            .ok_or(ProgramError::InvalidArgument),
+        Err(_) => Ok(0),
+    }
+}
+
+/// Moves `amount` from `source` to `destination` with `transfer_checked`, reading the
+/// decimals from `mint`. Every booth token movement goes through here, so the token program
+/// rejects a mint that doesn't match the accounts even if one of our checks is missed.
+/// `signer_seeds` is empty unless `authority` is a booth PDA.
+pub fn transfer_checked<'info>(
+    token_program: &AccountInfo<'info>,
+    source: &AccountInfo<'info>,
+    mint: &AccountInfo<'info>,
+    destination: &AccountInfo<'info>,
+    authority: &AccountInfo<'info>,
+    amount: u64,
+    signer_seeds: &[&[&[u8]]],
+) -> ProgramResult {
+    let transfer_ix = spl_token_2022::instruction::transfer_checked(
+        token_program.key,
+        source.key,
+        mint.key,
+        destination.key,
+        authority.key,
+        &[],
+        amount,
+        unpack_mint(mint)?.decimals)?;
+
+    invoke_signed(&transfer_ix,
+        &[source.clone(), mint.clone(), destination.clone(), authority.clone(), token_program.clone()],
+        signer_seeds)
+}
+
+/// Address of the temporary wrapped SOL account the booth opens for `user` when they pay in
+/// or get paid in lamports. It never outlives the instruction that opens it.
+pub fn find_wrapped_sol_address(exchange_booth: &Pubkey, user: &Pubkey, program_id: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"wsol", exchange_booth.as_ref(), user.as_ref()],
+        program_id,
+    )
+}
+
+/// Opens the user's wrapped SOL account for the native mint, owned by the user, holding
+/// `lamports` taken from the user's system account. The rent comes from the user as well,
+/// and both go back to the user in `unwrap_sol`.
+#[allow(clippy::too_many_arguments)]
+pub fn wrap_sol<'info>(
+    program_id: &Pubkey,
+    exchange_booth: &Pubkey,
+    user: &AccountInfo<'info>,
+    wsol_account: &AccountInfo<'info>,
+    native_mint: &AccountInfo<'info>,
+    token_program: &AccountInfo<'info>,
+    system_program: &AccountInfo<'info>,
+    lamports: u64,
+) -> ProgramResult {
+    assert_with_msg(
+        is_native_mint(native_mint.key),
+        ExchangeBoothError::NoNativeSide.into(),
+        "Lamports can only be paid into the native SOL side of a booth",
+    )?;
+    let bump = assert_pda(
+        wsol_account,
+        find_wrapped_sol_address(exchange_booth, user.key, program_id),
+        "Wrapped SOL account is not the PDA of the booth and user",
+    )?;
+    let seeds: &[&[u8]] = &[b"wsol", exchange_booth.as_ref(), user.key.as_ref(), &[bump]];
+
+    let len = token_account_len(native_mint)?;
+    let required = Rent::get()?
+        .minimum_balance(len)
+        .checked_add(lamports)
+        .ok_or(ProgramError::InvalidArgument)?;
+
+    create_pda_account(user, wsol_account, required, len, token_program.key, system_program, seeds)?;
+
+    // A native account's balance is its lamports above the rent exempt reserve
+    let init_ix = spl_token_2022::instruction::initialize_account3(
+        token_program.key,
+        wsol_account.key,
+        native_mint.key,
+        user.key)?;
+
+    invoke(&init_ix, &[wsol_account.clone(), native_mint.clone(), token_program.clone()])
+}
+
+/// Closes the user's wrapped SOL account, sending its balance and rent back to the user's
+/// system account.
+pub fn unwrap_sol<'info>(
+    token_program: &AccountInfo<'info>,
+    wsol_account: &AccountInfo<'info>,
+    user: &AccountInfo<'info>,
+) -> ProgramResult {
+    let close_ix = spl_token_2022::instruction::close_account(
+        token_program.key,
+        wsol_account.key,
+        user.key,
+        user.key,
+        &[])?;
+
+    invoke(&close_ix, &[wsol_account.clone(), user.clone(), token_program.clone()])
+}
@@ -0,0 +1,94 @@
+use borsh::BorshSerialize;
+use solana_program::pubkey::Pubkey;
+
+use exchangebooth::{events::BoothEvent, state::ConfigChange};
+
+fn data_line(event: &BoothEvent) -> String {
+    format!("Program data: {}", base64::encode(event.try_to_vec().unwrap()))
+}
+
+fn every_event() -> Vec<BoothEvent> {
+    let key = Pubkey::new_unique;
+    vec![
+        BoothEvent::Initialized {
+            exchange_booth: key(),
+            admin: key(),
+            mint_a: key(),
+            mint_b: key(),
+            oracle: key(),
+            spread_bps: 30,
+            flash_loan_fee_bps: 9,
+            referral_share_bps: 2_000,
+        },
+        BoothEvent::Deposited { exchange_booth: key(), depositor: key(), vault: key(), amount: 1_000 },
+        BoothEvent::Withdrawn { exchange_booth: key(), vault: key(), destination: key(), amount: 500 },
+        BoothEvent::Exchanged {
+            exchange_booth: key(),
+            user: key(),
+            input_mint: key(),
+            output_mint: key(),
+            amount_in: 100,
+            amount_out: 197,
+            fee: 3,
+            referrer: Some(key()),
+            referral_fee: 1,
+        },
+        BoothEvent::Exchanged {
+            exchange_booth: key(),
+            user: key(),
+            input_mint: key(),
+            output_mint: key(),
+            amount_in: 100,
+            amount_out: 49,
+            fee: 1,
+            referrer: None,
+            referral_fee: 0,
+        },
+        BoothEvent::Closed { exchange_booth: key(), admin: key() },
+        BoothEvent::ConfigChanged {
+            exchange_booth: key(),
+            change: ConfigChange::WithdrawalLimit { withdrawal_limit: 10, window_slots: 9_000 },
+        },
+    ]
+}
+
+#[test]
+fn decodes_logged_events() {
+    let program_id = Pubkey::new_unique();
+    let token_program = spl_token::id();
+    let events = every_event();
+
+    let mut logs = vec![
+        format!("Program {} invoke [1]", program_id),
+        "Program log: Instruction: Exchange".to_string(),
+        // neither base64 nor a Borsh encoded event
+        "Program data: not*base64".to_string(),
+        format!("Program data: {}", base64::encode([0xff, 1, 2])),
+        // data logged by a program the booth invokes is not the booth's
+        format!("Program {} invoke [2]", token_program),
+        data_line(&events[0]),
+        format!("Program {} consumed 4645 of 190000 compute units", token_program),
+        format!("Program {} success", token_program),
+    ];
+    logs.extend(events.iter().map(data_line));
+    logs.push(format!("Program {} success", program_id));
+    // nor is anything after the booth returns
+    logs.push(data_line(&events[1]));
+
+    assert_eq!(BoothEvent::decode_logs(&program_id, &logs), events);
+}
+
+#[test]
+fn decodes_every_field_of_a_data_line() {
+    let program_id = Pubkey::new_unique();
+    let events = every_event();
+    let fields: Vec<String> = events.iter().map(|event| base64::encode(event.try_to_vec().unwrap())).collect();
+
+    let logs = [
+        format!("Program {} invoke [1]", program_id),
+        format!("Program data: {}", fields.join(" ")),
+        format!("Program {} failed: custom program error: 0x1", program_id),
+    ];
+
+    assert_eq!(BoothEvent::decode_logs(&program_id, &logs), events);
+}
@@ -0,0 +1,537 @@
+use borsh::BorshDeserialize;
+use solana_program::{
+    bpf_loader_upgradeable,
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    system_program, sysvar,
+};
+
+use exchangebooth::{
+    instruction::{self, BoothKeys, ExchangeBoothInstruction},
+    state::ConfigChange,
+};
+
+// Every expected account list below follows the `next_account_info` order of the processor,
+// and PDAs are derived from their seeds here rather than through the state helpers.
+
+fn writable(key: Pubkey) -> AccountMeta {
+    AccountMeta::new(key, false)
+}
+
+fn readonly(key: Pubkey) -> AccountMeta {
+    AccountMeta::new_readonly(key, false)
+}
+
+fn writable_signer(key: Pubkey) -> AccountMeta {
+    AccountMeta::new(key, true)
+}
+
+fn readonly_signer(key: Pubkey) -> AccountMeta {
+    AccountMeta::new_readonly(key, true)
+}
+
+fn pda(seeds: &[&[u8]], program_id: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(seeds, program_id).0
+}
+
+fn vaults(exchange_booth: &Pubkey, program_id: &Pubkey) -> (Pubkey, Pubkey) {
+    (
+        pda(&[b"vault_a", exchange_booth.as_ref()], program_id),
+        pda(&[b"vault_b", exchange_booth.as_ref()], program_id),
+    )
+}
+
+fn registry(mint_a: &Pubkey, mint_b: &Pubkey, program_id: &Pubkey) -> Pubkey {
+    let (low, high) = if mint_a <= mint_b { (mint_a, mint_b) } else { (mint_b, mint_a) };
+    pda(&[b"registry", low.as_ref(), high.as_ref()], program_id)
+}
+
+fn decode(ix: &Instruction) -> ExchangeBoothInstruction {
+    ExchangeBoothInstruction::try_from_slice(&ix.data).unwrap()
+}
+
+fn booth_keys() -> BoothKeys {
+    BoothKeys {
+        exchange_booth: Pubkey::new_unique(),
+        mint_a: Pubkey::new_unique(),
+        mint_b: Pubkey::new_unique(),
+        oracle: Pubkey::new_unique(),
+        token_program: spl_token_2022::id(),
+    }
+}
+
+fn booth_metas(booth: &BoothKeys, program_id: &Pubkey) -> Vec<AccountMeta> {
+    let (vault_a, vault_b) = vaults(&booth.exchange_booth, program_id);
+    vec![
+        writable(booth.exchange_booth),
+        writable(vault_a),
+        writable(vault_b),
+        readonly(booth.mint_a),
+        readonly(booth.mint_b),
+        readonly(booth.oracle),
+    ]
+}
+
+#[test]
+fn initialize_exchange_booth() {
+    let program_id = Pubkey::new_unique();
+    let (admin, booth, mint_a, mint_b, oracle) =
+        (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+    let ix = instruction::initialize_exchange_booth(
+        &program_id, &admin, &booth, &mint_a, &mint_b, &oracle, &spl_token::id(), 30, 9, 2_000, 100, 9_000,
+    );
+
+    let (vault_a, vault_b) = vaults(&booth, &program_id);
+    assert_eq!(ix.program_id, program_id);
+    assert_eq!(
+        ix.accounts,
+        vec![
+            writable_signer(admin),
+            writable(booth),
+            readonly(mint_a),
+            readonly(mint_b),
+            writable(vault_a),
+            writable(vault_b),
+            readonly(oracle),
+            readonly(system_program::id()),
+            readonly(spl_token::id()),
+            readonly(sysvar::rent::id()),
+            writable(registry(&mint_a, &mint_b, &program_id)),
+            writable(pda(&[b"registry_index"], &program_id)),
+        ],
+    );
+    assert!(matches!(
+        decode(&ix),
+        ExchangeBoothInstruction::InititializeExchangeBooth {
+            spread_bps: 30,
+            flash_loan_fee_bps: 9,
+            referral_share_bps: 2_000,
+            withdrawal_limit: 100,
+            withdrawal_window_slots: 9_000,
+        }
+    ));
+}
+
+#[test]
+fn deposit() {
+    let program_id = Pubkey::new_unique();
+    let (depositor, depositor_token, vault, mint, booth) =
+        (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+    let ix = instruction::deposit(&program_id, &depositor, &depositor_token, &vault, &mint, &booth, &spl_token::id(), 7);
+
+    let token_accounts = vec![
+        writable_signer(depositor),
+        writable(depositor_token),
+        writable(vault),
+        readonly(mint),
+        readonly(booth),
+        readonly(spl_token::id()),
+    ];
+    assert_eq!(ix.accounts, token_accounts);
+    assert!(matches!(decode(&ix), ExchangeBoothInstruction::Deposit { amount: 7 }));
+
+    // lamports go through the wrapped SOL PDA, which takes the system program as well
+    let native_mint = spl_token::native_mint::id();
+    let wsol = pda(&[b"wsol", booth.as_ref(), depositor.as_ref()], &program_id);
+    let ix = instruction::deposit(&program_id, &depositor, &wsol, &vault, &native_mint, &booth, &spl_token::id(), 7);
+    assert_eq!(
+        ix.accounts,
+        vec![
+            writable_signer(depositor),
+            writable(wsol),
+            writable(vault),
+            readonly(native_mint),
+            readonly(booth),
+            readonly(spl_token::id()),
+            readonly(system_program::id()),
+        ],
+    );
+}
+
+#[test]
+fn withdraw() {
+    let program_id = Pubkey::new_unique();
+    let (admin, admin_token, vault, mint, booth) =
+        (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+    let accounts = vec![
+        writable(admin_token),
+        writable(vault),
+        readonly(mint),
+        writable(booth),
+        readonly(spl_token::id()),
+    ];
+
+    let ix = instruction::withdraw(&program_id, &admin, &admin_token, &vault, &mint, &booth, &spl_token::id(), &[], 5);
+    let mut expected = vec![readonly_signer(admin)];
+    expected.extend(accounts.clone());
+    assert_eq!(ix.accounts, expected);
+    assert!(matches!(decode(&ix), ExchangeBoothInstruction::Withdraw { amount_to_withdraw: 5 }));
+
+    // a multisig admin doesn't sign, its signers trail the accounts
+    let multisig = pda(&[b"multisig", booth.as_ref()], &program_id);
+    let (signer_1, signer_2) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let ix = instruction::withdraw(
+        &program_id, &multisig, &admin_token, &vault, &mint, &booth, &spl_token::id(), &[&signer_1, &signer_2], 5,
+    );
+    let mut expected = vec![readonly(multisig)];
+    expected.extend(accounts);
+    expected.extend([readonly_signer(signer_1), readonly_signer(signer_2)]);
+    assert_eq!(ix.accounts, expected);
+}
+
+#[test]
+fn exchange() {
+    let program_id = Pubkey::new_unique();
+    let booth = booth_keys();
+    let (user, source, destination, referrer) =
+        (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+    let access = instruction::allowlist_access(&program_id, &booth.exchange_booth, &user);
+    assert_eq!(access, pda(&[b"allowlist", booth.exchange_booth.as_ref(), user.as_ref()], &program_id));
+
+    let ix = instruction::exchange(&program_id, &user, &source, &destination, &booth, &access, None, 100, 90);
+    let mut expected = vec![writable_signer(user), writable(source), writable(destination)];
+    expected.extend(booth_metas(&booth, &program_id));
+    expected.extend([
+        readonly(booth.token_program),
+        readonly(pda(&[b"protocol_config"], &program_id)),
+        writable(pda(&[b"user_volume", booth.exchange_booth.as_ref(), user.as_ref()], &program_id)),
+        readonly(system_program::id()),
+        readonly(access),
+    ]);
+    assert_eq!(ix.accounts, expected);
+    assert!(matches!(
+        decode(&ix),
+        ExchangeBoothInstruction::Exchange { tokens_to_transfer: 100, min_amount_out: 90 }
+    ));
+
+    // the referrer is the optional last account
+    let ix = instruction::exchange(&program_id, &user, &source, &destination, &booth, &access, Some(&referrer), 100, 90);
+    expected.push(writable(referrer));
+    assert_eq!(ix.accounts, expected);
+}
+
+#[test]
+fn quote() {
+    let program_id = Pubkey::new_unique();
+    let booth = booth_keys();
+    let (user, source, destination, access) =
+        (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+    let ix = instruction::quote(&program_id, &user, &source, &destination, &booth, &access, 100);
+
+    // the accounts of an exchange, none of them signing or written
+    let exchange = instruction::exchange(&program_id, &user, &source, &destination, &booth, &access, None, 100, 0);
+    let expected: Vec<AccountMeta> = exchange.accounts.iter().map(|meta| readonly(meta.pubkey)).collect();
+    assert_eq!(ix.accounts, expected);
+    assert!(matches!(decode(&ix), ExchangeBoothInstruction::Quote { tokens_to_transfer: 100 }));
+}
+
+#[test]
+fn close_exchange_booth() {
+    let program_id = Pubkey::new_unique();
+    let booth = booth_keys();
+    let (admin, rent_receiver) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let ix = instruction::close_exchange_booth(&program_id, &admin, &booth, &rent_receiver, &[]);
+
+    let (vault_a, vault_b) = vaults(&booth.exchange_booth, &program_id);
+    assert_eq!(
+        ix.accounts,
+        vec![
+            readonly_signer(admin),
+            writable(booth.exchange_booth),
+            writable(vault_a),
+            writable(vault_b),
+            writable(registry(&booth.mint_a, &booth.mint_b, &program_id)),
+            readonly(booth.token_program),
+            writable(rent_receiver),
+        ],
+    );
+    assert!(matches!(decode(&ix), ExchangeBoothInstruction::CloseExchangeBooth {}));
+}
+
+#[test]
+fn route_exchange() {
+    let program_id = Pubkey::new_unique();
+    let (first, second) = (booth_keys(), booth_keys());
+    let (user, source, destination, access_1, access_2) =
+        (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+    let ix = instruction::route_exchange(
+        &program_id, &user, &source, &destination, &[(first, access_1), (second, access_2)], 100, 90,
+    );
+
+    let mut expected = vec![
+        writable_signer(user),
+        writable(source),
+        writable(destination),
+        readonly(first.token_program),
+        readonly(pda(&[b"protocol_config"], &program_id)),
+        readonly(system_program::id()),
+    ];
+    for (booth, access) in [(first, access_1), (second, access_2)] {
+        expected.extend(booth_metas(&booth, &program_id));
+        expected.push(writable(pda(&[b"user_volume", booth.exchange_booth.as_ref(), user.as_ref()], &program_id)));
+        expected.push(readonly(access));
+    }
+    assert_eq!(ix.accounts, expected);
+    assert!(matches!(
+        decode(&ix),
+        ExchangeBoothInstruction::RouteExchange { amount_in: 100, min_amount_out: 90 }
+    ));
+}
+
+#[test]
+fn flash_borrow_and_repay() {
+    let program_id = Pubkey::new_unique();
+    let (borrower, borrower_token, booth, vault, mint) =
+        (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+    let mut expected = vec![
+        readonly_signer(borrower),
+        writable(borrower_token),
+        writable(booth),
+        writable(vault),
+        readonly(mint),
+        readonly(spl_token::id()),
+    ];
+
+    let repay =
+        instruction::flash_repay(&program_id, &borrower, &borrower_token, &booth, &vault, &mint, &spl_token::id(), 50);
+    assert_eq!(repay.accounts, expected);
+    assert!(matches!(decode(&repay), ExchangeBoothInstruction::FlashRepay { amount: 50 }));
+
+    let borrow =
+        instruction::flash_borrow(&program_id, &borrower, &borrower_token, &booth, &vault, &mint, &spl_token::id(), 50);
+    expected.push(readonly(sysvar::instructions::id()));
+    assert_eq!(borrow.accounts, expected);
+    assert!(matches!(decode(&borrow), ExchangeBoothInstruction::FlashBorrow { amount: 50 }));
+}
+
+#[test]
+fn set_protocol_config() {
+    let program_id = Pubkey::new_unique();
+    let (authority, treasury_owner) = (Pubkey::new_unique(), Pubkey::new_unique());
+    let ix = instruction::set_protocol_config(&program_id, &authority, &treasury_owner, 1_000);
+
+    assert_eq!(
+        ix.accounts,
+        vec![
+            writable_signer(authority),
+            writable(pda(&[b"protocol_config"], &program_id)),
+            readonly(system_program::id()),
+            readonly(pda(&[program_id.as_ref()], &bpf_loader_upgradeable::id())),
+        ],
+    );
+    assert!(matches!(
+        decode(&ix),
+        ExchangeBoothInstruction::SetProtocolConfig { treasury_owner: owner, protocol_fee_share_bps: 1_000 }
+            if owner == treasury_owner
+    ));
+}
+
+#[test]
+fn collect_protocol_fees() {
+    let program_id = Pubkey::new_unique();
+    let treasury_owner = Pubkey::new_unique();
+    let booths: Vec<_> = (0..2)
+        .map(|_| {
+            (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique())
+        })
+        .collect();
+    let ix = instruction::collect_protocol_fees(&program_id, &treasury_owner, &spl_token::id(), &booths);
+
+    let mut expected = vec![
+        readonly_signer(treasury_owner),
+        readonly(pda(&[b"protocol_config"], &program_id)),
+        readonly(spl_token::id()),
+    ];
+    for (booth, mint_a, mint_b, treasury_a, treasury_b) in booths {
+        let (vault_a, vault_b) = vaults(&booth, &program_id);
+        expected.extend([
+            writable(booth),
+            writable(vault_a),
+            writable(vault_b),
+            readonly(mint_a),
+            readonly(mint_b),
+            writable(treasury_a),
+            writable(treasury_b),
+        ]);
+    }
+    assert_eq!(ix.accounts, expected);
+    assert!(matches!(decode(&ix), ExchangeBoothInstruction::CollectProtocolFees {}));
+}
+
+#[test]
+fn admin_handover_and_pausing() {
+    let program_id = Pubkey::new_unique();
+    let (admin, new_admin, booth) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+
+    let ix = instruction::propose_admin(&program_id, &admin, &booth, &[], &new_admin);
+    assert_eq!(ix.accounts, vec![readonly_signer(admin), writable(booth)]);
+    assert!(matches!(decode(&ix), ExchangeBoothInstruction::ProposeAdmin { new_admin: key } if key == new_admin));
+
+    let ix = instruction::accept_admin(&program_id, &new_admin, &booth, &[]);
+    assert_eq!(ix.accounts, vec![readonly_signer(new_admin), writable(booth)]);
+    assert!(matches!(decode(&ix), ExchangeBoothInstruction::AcceptAdmin {}));
+
+    let signer = Pubkey::new_unique();
+    let ix = instruction::set_pause_flags(&program_id, &admin, &booth, &[&signer], 3);
+    assert_eq!(ix.accounts, vec![readonly(admin), writable(booth), readonly_signer(signer)]);
+    assert!(matches!(decode(&ix), ExchangeBoothInstruction::SetPauseFlags { pause_flags: 3 }));
+}
+
+#[test]
+fn set_admin_multisig() {
+    let program_id = Pubkey::new_unique();
+    let (admin, booth, payer) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+    let new_signers = vec![Pubkey::new_unique(), Pubkey::new_unique()];
+    let ix = instruction::set_admin_multisig(&program_id, &admin, &booth, &payer, &[], new_signers.clone(), 2);
+
+    assert_eq!(
+        ix.accounts,
+        vec![
+            readonly_signer(admin),
+            writable(booth),
+            writable(pda(&[b"multisig", booth.as_ref()], &program_id)),
+            writable_signer(payer),
+            readonly(system_program::id()),
+        ],
+    );
+    assert!(matches!(
+        decode(&ix),
+        ExchangeBoothInstruction::SetAdminMultisig { signers, threshold: 2 } if signers == new_signers
+    ));
+}
+
+#[test]
+fn config_change_queue() {
+    let program_id = Pubkey::new_unique();
+    let (admin, booth, payer) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+    let queued_change = pda(&[b"config_change", booth.as_ref(), &4u64.to_le_bytes()], &program_id);
+
+    let ix = instruction::queue_config_change(
+        &program_id, &admin, &booth, &payer, &[], 4, ConfigChange::SpreadBps(50), 1_000,
+    );
+    assert_eq!(
+        ix.accounts,
+        vec![
+            readonly_signer(admin),
+            readonly(booth),
+            writable(queued_change),
+            writable_signer(payer),
+            readonly(system_program::id()),
+        ],
+    );
+    assert!(matches!(
+        decode(&ix),
+        ExchangeBoothInstruction::QueueConfigChange {
+            change_id: 4,
+            change: ConfigChange::SpreadBps(50),
+            earliest_apply_slot: 1_000,
+        }
+    ));
+
+    // anyone may apply a change once it is due, the rent goes back to its payer
+    let ix = instruction::apply_config_change(&program_id, &booth, 4, &payer);
+    assert_eq!(ix.accounts, vec![writable(booth), writable(queued_change), writable(payer)]);
+    assert!(matches!(decode(&ix), ExchangeBoothInstruction::ApplyConfigChange {}));
+
+    let ix = instruction::cancel_config_change(&program_id, &admin, &booth, 4, &payer, &[]);
+    assert_eq!(
+        ix.accounts,
+        vec![readonly_signer(admin), readonly(booth), writable(queued_change), writable(payer)],
+    );
+    assert!(matches!(decode(&ix), ExchangeBoothInstruction::CancelConfigChange {}));
+}
+
+#[test]
+fn set_allowlist_entry() {
+    let program_id = Pubkey::new_unique();
+    let (admin, booth, payer, user) =
+        (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+    let ix = instruction::set_allowlist_entry(&program_id, &admin, &booth, &payer, &[], &user, true);
+
+    assert_eq!(
+        ix.accounts,
+        vec![
+            readonly_signer(admin),
+            readonly(booth),
+            writable(pda(&[b"allowlist", booth.as_ref(), user.as_ref()], &program_id)),
+            writable_signer(payer),
+            readonly(system_program::id()),
+        ],
+    );
+    assert!(matches!(
+        decode(&ix),
+        ExchangeBoothInstruction::SetAllowlistEntry { user: key, allowed: true } if key == user
+    ));
+}
+
+#[test]
+fn withdrawal_queue() {
+    let program_id = Pubkey::new_unique();
+    let (admin, booth, vault, mint, destination, payer) = (
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+        Pubkey::new_unique(),
+    );
+    let queued_withdrawal = pda(&[b"withdrawal", booth.as_ref(), &9u64.to_le_bytes()], &program_id);
+
+    let ix = instruction::queue_withdrawal(&program_id, &admin, &booth, &vault, &destination, &payer, &[], 9, 300);
+    assert_eq!(
+        ix.accounts,
+        vec![
+            readonly_signer(admin),
+            readonly(booth),
+            writable(queued_withdrawal),
+            readonly(vault),
+            readonly(destination),
+            writable_signer(payer),
+            readonly(system_program::id()),
+        ],
+    );
+    assert!(matches!(decode(&ix), ExchangeBoothInstruction::QueueWithdrawal { withdrawal_id: 9, amount: 300 }));
+
+    let ix =
+        instruction::execute_withdrawal(&program_id, &booth, 9, &vault, &mint, &destination, &payer, &spl_token::id());
+    assert_eq!(
+        ix.accounts,
+        vec![
+            readonly(booth),
+            writable(queued_withdrawal),
+            writable(vault),
+            readonly(mint),
+            writable(destination),
+            writable(payer),
+            readonly(spl_token::id()),
+        ],
+    );
+    assert!(matches!(decode(&ix), ExchangeBoothInstruction::ExecuteWithdrawal {}));
+
+    let ix = instruction::cancel_withdrawal(&program_id, &admin, &booth, 9, &payer, &[]);
+    assert_eq!(
+        ix.accounts,
+        vec![readonly_signer(admin), readonly(booth), writable(queued_withdrawal), writable(payer)],
+    );
+    assert!(matches!(decode(&ix), ExchangeBoothInstruction::CancelWithdrawal {}));
+}
+
+#[test]
+fn migrate() {
+    let program_id = Pubkey::new_unique();
+    let (payer, booth, mint_a, mint_b) =
+        (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+    let ix = instruction::migrate(&program_id, &payer, &booth, &mint_a, &mint_b);
+
+    assert_eq!(
+        ix.accounts,
+        vec![
+            writable_signer(payer),
+            writable(booth),
+            readonly(system_program::id()),
+            writable(registry(&mint_a, &mint_b, &program_id)),
+            writable(pda(&[b"registry_index"], &program_id)),
+        ],
+    );
+    assert!(matches!(decode(&ix), ExchangeBoothInstruction::Migrate {}));
+}
@@ -0,0 +1,132 @@
+use borsh::BorshSerialize;
+use solana_program::{
+    instruction::{Instruction, InstructionError}, pubkey::Pubkey, rent::Rent,
+};
+use solana_program_test::{processor, tokio, BanksClient, ProgramTest};
+use solana_sdk::{
+    account::Account,
+    hash::Hash,
+    signature::{Keypair, Signer},
+    transaction::{Transaction, TransactionError},
+};
+
+use exchangebooth::{
+    error::ExchangeBoothError,
+    instruction,
+    processor::Processor,
+    state::{AccountState, BoothRegistry, ExchangeBooth, RegistryIndex, ACCOUNT_HEADER_LEN},
+};
+
+/// The booth as the first `InititializeExchangeBooth` wrote it, which never set `initialized`.
+#[derive(BorshSerialize)]
+struct BaselineExchangeBooth {
+    initialized: bool,
+    admin: Pubkey,
+    mint_a: Pubkey,
+    mint_b: Pubkey,
+    vault_a: Pubkey,
+    vault_b: Pubkey,
+    oracle: Pubkey,
+}
+
+fn baseline_booth(program_id: &Pubkey, booth: &Pubkey, keys: &[Pubkey; 4]) -> Account {
+    let data = BaselineExchangeBooth {
+        initialized: false,
+        admin: keys[0],
+        mint_a: keys[1],
+        mint_b: keys[2],
+        vault_a: ExchangeBooth::find_vault_address(booth, b"vault_a", program_id).0,
+        vault_b: ExchangeBooth::find_vault_address(booth, b"vault_b", program_id).0,
+        oracle: keys[3],
+    }
+    .try_to_vec()
+    .unwrap();
+    assert_eq!(data.len(), ExchangeBooth::UNVERSIONED_LEN);
+    program_account(program_id, data)
+}
+
+fn program_account(program_id: &Pubkey, data: Vec<u8>) -> Account {
+    Account {
+        lamports: Rent::default().minimum_balance(data.len()),
+        data,
+        owner: *program_id,
+        ..Account::default()
+    }
+}
+
+async fn send(banks_client: &mut BanksClient, payer: &Keypair, blockhash: Hash, ix: Instruction) -> Result<(), TransactionError> {
+    let tx = Transaction::new_signed_with_payer(&[ix], Some(&payer.pubkey()), &[payer], blockhash);
+    banks_client.process_transaction(tx).await.map_err(|err| err.unwrap())
+}
+
+async fn data(banks_client: &mut BanksClient, address: Pubkey) -> Vec<u8> {
+    banks_client.get_account(address).await.unwrap().unwrap().data
+}
+
+fn invalid_account_data() -> TransactionError {
+    TransactionError::InstructionError(0, InstructionError::Custom(ExchangeBoothError::InvalidAccountData as u32))
+}
+
+#[tokio::test]
+async fn migrates_baseline_booths() {
+    let program_id = Pubkey::new_unique();
+    let mut program_test = ProgramTest::new("exchangebooth", program_id, processor!(Processor::process_instruction));
+
+    let keys = [Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique()];
+    let (mint_a, mint_b) = (keys[1], keys[2]);
+    let (booth, second_booth) = (Pubkey::new_unique(), Pubkey::new_unique());
+    program_test.add_account(booth, baseline_booth(&program_id, &booth, &keys));
+    program_test.add_account(second_booth, baseline_booth(&program_id, &second_booth, &keys));
+
+    // the same size, but its vaults aren't the booth's vault PDAs
+    let impostor = Pubkey::new_unique();
+    let mut impostor_data = vec![0; ExchangeBooth::UNVERSIONED_LEN];
+    impostor_data[1..].iter_mut().enumerate().for_each(|(i, byte)| *byte = i as u8);
+    program_test.add_account(impostor, program_account(&program_id, impostor_data));
+
+    let unknown = Pubkey::new_unique();
+    program_test.add_account(unknown, program_account(&program_id, vec![9; 10]));
+
+    let (mut banks_client, payer, blockhash) = program_test.start().await;
+    let migrate = |account: &Pubkey| instruction::migrate(&program_id, &payer.pubkey(), account, &mint_a, &mint_b);
+
+    // the booth keeps its keys behind the header and joins its pair's registry
+    send(&mut banks_client, &payer, blockhash, migrate(&booth)).await.unwrap();
+    let migrated = data(&mut banks_client, booth).await;
+    assert_eq!(migrated.len(), ExchangeBooth::LEN);
+    assert_eq!(migrated[..ACCOUNT_HEADER_LEN], [ExchangeBooth::DISCRIMINATOR, ExchangeBooth::VERSION]);
+    let exchange_booth: &ExchangeBooth = bytemuck::from_bytes(&migrated[ACCOUNT_HEADER_LEN..]);
+    let (vault_a, vault_b) = (
+        ExchangeBooth::find_vault_address(&booth, b"vault_a", &program_id).0,
+        ExchangeBooth::find_vault_address(&booth, b"vault_b", &program_id).0,
+    );
+    assert_eq!(
+        [exchange_booth.admin, exchange_booth.mint_a, exchange_booth.mint_b, exchange_booth.vault_a,
+            exchange_booth.vault_b, exchange_booth.oracle],
+        [keys[0], mint_a, mint_b, vault_a, vault_b, keys[3]],
+    );
+    assert_eq!((exchange_booth.initialized, exchange_booth.withdrawal_limit), (1, 0));
+
+    let (registry_address, _) = BoothRegistry::find_address(&mint_a, &mint_b, &program_id);
+    send(&mut banks_client, &payer, blockhash, migrate(&second_booth)).await.unwrap();
+    let registry = BoothRegistry::unpack_account(&data(&mut banks_client, registry_address).await).unwrap();
+    assert_eq!(registry.booths, vec![booth, second_booth]);
+    let (registry_index, _) = RegistryIndex::find_address(&program_id);
+    let index = RegistryIndex::unpack_account(&data(&mut banks_client, registry_index).await).unwrap();
+    assert_eq!(index.registries, vec![registry_address]);
+
+    // migrating a current booth is a no-op, and registers it no second time
+    let tx = Transaction::new_signed_with_payer(
+        &[migrate(&booth), migrate(&booth)],
+        Some(&payer.pubkey()),
+        &[&payer],
+        blockhash,
+    );
+    banks_client.process_transaction(tx).await.unwrap();
+    assert_eq!(data(&mut banks_client, booth).await, migrated);
+    let registry = BoothRegistry::unpack_account(&data(&mut banks_client, registry_address).await).unwrap();
+    assert_eq!(registry.booths, vec![booth, second_booth]);
+
+    assert_eq!(send(&mut banks_client, &payer, blockhash, migrate(&impostor)).await, Err(invalid_account_data()));
+    assert_eq!(send(&mut banks_client, &payer, blockhash, migrate(&unknown)).await, Err(invalid_account_data()));
+}
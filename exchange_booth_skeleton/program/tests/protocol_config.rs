@@ -0,0 +1,91 @@
+use solana_program::{
+    bpf_loader_upgradeable::{self, UpgradeableLoaderState},
+    instruction::{Instruction, InstructionError},
+    pubkey::Pubkey,
+    rent::Rent,
+    system_program,
+};
+use solana_program_test::{processor, tokio, BanksClient, ProgramTest};
+use solana_sdk::{
+    account::Account,
+    hash::Hash,
+    signature::{Keypair, Signer},
+    transaction::{Transaction, TransactionError},
+};
+
+use exchangebooth::{
+    error::ExchangeBoothError,
+    instruction,
+    processor::Processor,
+    state::{AccountState, ProtocolConfig},
+};
+
+/// A ProgramData account as the upgradeable loader writes it, without the program bytes.
+fn program_data_account(upgrade_authority: &Pubkey) -> Account {
+    let mut data = 3u32.to_le_bytes().to_vec();
+    data.extend(0u64.to_le_bytes());
+    data.push(1);
+    data.extend(upgrade_authority.to_bytes());
+    assert_eq!(data.len(), UpgradeableLoaderState::size_of_programdata_metadata());
+    Account {
+        lamports: Rent::default().minimum_balance(data.len()),
+        data,
+        owner: bpf_loader_upgradeable::id(),
+        ..Account::default()
+    }
+}
+
+async fn send(
+    banks_client: &mut BanksClient,
+    payer: &Keypair,
+    signer: &Keypair,
+    blockhash: Hash,
+    ix: Instruction,
+) -> Result<(), TransactionError> {
+    let tx = Transaction::new_signed_with_payer(&[ix], Some(&payer.pubkey()), &[payer, signer], blockhash);
+    banks_client.process_transaction(tx).await.map_err(|err| err.unwrap())
+}
+
+#[tokio::test]
+async fn only_the_upgrade_authority_creates_the_config() {
+    let program_id = Pubkey::new_unique();
+    let mut program_test = ProgramTest::new("exchangebooth", program_id, processor!(Processor::process_instruction));
+
+    let (upgrade_authority, attacker) = (Keypair::new(), Keypair::new());
+    for signer in [&upgrade_authority, &attacker] {
+        program_test.add_account(
+            signer.pubkey(),
+            Account { lamports: 1_000_000_000, owner: system_program::id(), ..Account::default() },
+        );
+    }
+    let (program_data, _) = Pubkey::find_program_address(&[program_id.as_ref()], &bpf_loader_upgradeable::id());
+    program_test.add_account(program_data, program_data_account(&upgrade_authority.pubkey()));
+
+    let (mut banks_client, payer, blockhash) = program_test.start().await;
+    let (protocol_config, _) = ProtocolConfig::find_address(&program_id);
+
+    assert_eq!(
+        send(
+            &mut banks_client, &payer, &attacker, blockhash,
+            instruction::set_protocol_config(&program_id, &attacker.pubkey(), &attacker.pubkey(), 10_000),
+        )
+        .await,
+        Err(TransactionError::InstructionError(
+            0,
+            InstructionError::Custom(ExchangeBoothError::IncorrectAdmin as u32),
+        )),
+    );
+    assert!(banks_client.get_account(protocol_config).await.unwrap().is_none());
+
+    let treasury_owner = Pubkey::new_unique();
+    send(
+        &mut banks_client, &payer, &upgrade_authority, blockhash,
+        instruction::set_protocol_config(&program_id, &upgrade_authority.pubkey(), &treasury_owner, 1_000),
+    )
+    .await
+    .unwrap();
+
+    let data = banks_client.get_account(protocol_config).await.unwrap().unwrap().data;
+    let config = ProtocolConfig::unpack_account(&data).unwrap();
+    assert_eq!((config.treasury_owner, config.protocol_fee_share_bps), (treasury_owner, 1_000));
+}
@@ -0,0 +1,132 @@
+use solana_program::{
+    instruction::Instruction, program_option::COption, program_pack::Pack, pubkey::Pubkey,
+    rent::Rent, system_program,
+};
+use solana_program_test::{processor, tokio, BanksClient, ProgramTest};
+use solana_sdk::{
+    account::Account,
+    hash::Hash,
+    signature::{Keypair, Signer},
+    transaction::Transaction,
+};
+use spl_token::state::Mint;
+
+use exchangebooth::{
+    instruction::{self, BoothKeys},
+    processor::Processor,
+    state::{AccountState, BoothRegistry, ExchangeBooth, RegistryIndex},
+};
+
+fn mint_account() -> Account {
+    let mut data = vec![0; Mint::LEN];
+    Mint {
+        mint_authority: COption::Some(Pubkey::new_unique()),
+        supply: 0,
+        decimals: 6,
+        is_initialized: true,
+        freeze_authority: COption::None,
+    }
+    .pack_into_slice(&mut data);
+    Account { lamports: Rent::default().minimum_balance(Mint::LEN), data, owner: spl_token::id(), ..Account::default() }
+}
+
+fn booth_account(program_id: &Pubkey) -> Account {
+    Account {
+        lamports: Rent::default().minimum_balance(ExchangeBooth::LEN),
+        data: vec![0; ExchangeBooth::LEN],
+        owner: *program_id,
+        ..Account::default()
+    }
+}
+
+async fn send(banks_client: &mut BanksClient, payer: &Keypair, admin: &Keypair, blockhash: Hash, ix: Instruction) {
+    let tx = Transaction::new_signed_with_payer(&[ix], Some(&payer.pubkey()), &[payer, admin], blockhash);
+    banks_client.process_transaction(tx).await.unwrap();
+}
+
+async fn registry(banks_client: &mut BanksClient, address: Pubkey) -> (BoothRegistry, usize) {
+    let account = banks_client.get_account(address).await.unwrap().unwrap();
+    (BoothRegistry::unpack_account(&account.data).unwrap(), account.data.len())
+}
+
+#[tokio::test]
+async fn registers_booths_by_mint_pair() {
+    let program_id = Pubkey::new_unique();
+    let mut program_test = ProgramTest::new("exchangebooth", program_id, processor!(Processor::process_instruction));
+
+    let admin = Keypair::new();
+    let (mint_a, mint_b, mint_c, oracle) =
+        (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+    let booths = [Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique()];
+    let (registry_ab, _) = BoothRegistry::find_address(&mint_a, &mint_b, &program_id);
+    let (registry_ac, _) = BoothRegistry::find_address(&mint_a, &mint_c, &program_id);
+    assert_eq!(BoothRegistry::find_address(&mint_b, &mint_a, &program_id).0, registry_ab);
+
+    program_test.add_account(
+        admin.pubkey(),
+        Account { lamports: 10_000_000_000, owner: system_program::id(), ..Account::default() },
+    );
+    for mint in [mint_a, mint_b, mint_c] {
+        program_test.add_account(mint, mint_account());
+    }
+    for booth in booths {
+        program_test.add_account(booth, booth_account(&program_id));
+    }
+    // lamports sent to a registry address ahead of time must not block the pair
+    program_test.add_account(
+        registry_ac,
+        Account { lamports: 1_000_000, owner: system_program::id(), ..Account::default() },
+    );
+
+    let (mut banks_client, payer, blockhash) = program_test.start().await;
+    let keys = [
+        BoothKeys { exchange_booth: booths[0], mint_a, mint_b, oracle, token_program: spl_token::id() },
+        // the pair's registry is the same whichever mint the booth calls mint_a
+        BoothKeys { exchange_booth: booths[1], mint_a: mint_b, mint_b: mint_a, oracle, token_program: spl_token::id() },
+        BoothKeys { exchange_booth: booths[2], mint_a, mint_b: mint_c, oracle, token_program: spl_token::id() },
+    ];
+    for booth in &keys {
+        let ix = instruction::initialize_exchange_booth(
+            &program_id, &admin.pubkey(), &booth.exchange_booth, &booth.mint_a, &booth.mint_b, &oracle,
+            &spl_token::id(), 30, 0, 0, 0, 0,
+        );
+        send(&mut banks_client, &payer, &admin, blockhash, ix).await;
+    }
+
+    let (pair, len) = registry(&mut banks_client, registry_ab).await;
+    assert_eq!((pair.mint_a, pair.mint_b), BoothRegistry::sorted_pair(&mint_a, &mint_b));
+    assert_eq!(pair.booths, vec![booths[0], booths[1]]);
+    assert_eq!(len, BoothRegistry::len(2));
+    let (pair, _) = registry(&mut banks_client, registry_ac).await;
+    assert_eq!(pair.booths, vec![booths[2]]);
+
+    // every pair's registry is listed once in the index
+    let (registry_index, _) = RegistryIndex::find_address(&program_id);
+    let index = banks_client.get_account(registry_index).await.unwrap().unwrap();
+    assert_eq!(RegistryIndex::unpack_account(&index.data).unwrap().registries, vec![registry_ab, registry_ac]);
+    assert_eq!(index.data.len(), RegistryIndex::len(2));
+
+    let rent_receiver = Pubkey::new_unique();
+    let ix = instruction::close_exchange_booth(&program_id, &admin.pubkey(), &keys[0], &rent_receiver, &[]);
+    send(&mut banks_client, &payer, &admin, blockhash, ix).await;
+    let (pair, len) = registry(&mut banks_client, registry_ab).await;
+    assert_eq!(pair.booths, vec![booths[1]]);
+    assert_eq!(len, BoothRegistry::len(1));
+
+    // the booth, both vaults and the registry's freed key all pay out to the rent receiver
+    let rent = Rent::default();
+    assert_eq!(
+        banks_client.get_balance(rent_receiver).await.unwrap(),
+        rent.minimum_balance(ExchangeBooth::LEN)
+            + 2 * rent.minimum_balance(spl_token::state::Account::LEN)
+            + rent.minimum_balance(BoothRegistry::len(2))
+            - rent.minimum_balance(BoothRegistry::len(1)),
+    );
+
+    // a booth that was never registered under the pair can't be removed from it
+    let mut wrong_pair = keys[2];
+    wrong_pair.mint_b = mint_b;
+    let ix = instruction::close_exchange_booth(&program_id, &admin.pubkey(), &wrong_pair, &rent_receiver, &[]);
+    let tx = Transaction::new_signed_with_payer(&[ix], Some(&payer.pubkey()), &[&payer, &admin], blockhash);
+    assert!(banks_client.process_transaction(tx).await.is_err());
+}
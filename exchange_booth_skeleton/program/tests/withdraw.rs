@@ -0,0 +1,123 @@
+use solana_program::{
+    instruction::{Instruction, InstructionError}, program_option::COption, program_pack::Pack, pubkey::Pubkey,
+    rent::Rent, system_program,
+};
+use solana_program_test::{processor, tokio, BanksClient, ProgramTest};
+use solana_sdk::{
+    account::Account,
+    hash::Hash,
+    signature::{Keypair, Signer},
+    transaction::{Transaction, TransactionError},
+};
+use spl_token::state::{Account as TokenAccount, AccountState, Mint};
+
+use exchangebooth::{error::ExchangeBoothError, instruction, processor::Processor, state::ExchangeBooth};
+
+fn mint_account() -> Account {
+    let mut data = vec![0; Mint::LEN];
+    Mint {
+        mint_authority: COption::Some(Pubkey::new_unique()),
+        supply: 1_000_000,
+        decimals: 6,
+        is_initialized: true,
+        freeze_authority: COption::None,
+    }
+    .pack_into_slice(&mut data);
+    Account { lamports: Rent::default().minimum_balance(Mint::LEN), data, owner: spl_token::id(), ..Account::default() }
+}
+
+fn token_account(mint: &Pubkey, owner: &Pubkey, amount: u64) -> Account {
+    let mut data = vec![0; TokenAccount::LEN];
+    TokenAccount { mint: *mint, owner: *owner, amount, state: AccountState::Initialized, ..TokenAccount::default() }
+        .pack_into_slice(&mut data);
+    Account {
+        lamports: Rent::default().minimum_balance(TokenAccount::LEN),
+        data,
+        owner: spl_token::id(),
+        ..Account::default()
+    }
+}
+
+async fn send(
+    banks_client: &mut BanksClient,
+    payer: &Keypair,
+    admin: &Keypair,
+    blockhash: Hash,
+    ixs: &[Instruction],
+) -> Result<(), TransactionError> {
+    let tx = Transaction::new_signed_with_payer(ixs, Some(&payer.pubkey()), &[payer, admin], blockhash);
+    banks_client.process_transaction(tx).await.map_err(|err| err.unwrap())
+}
+
+fn limit_exceeded(index: u8) -> TransactionError {
+    TransactionError::InstructionError(
+        index,
+        InstructionError::Custom(ExchangeBoothError::WithdrawalLimitExceeded as u32),
+    )
+}
+
+#[tokio::test]
+async fn limits_direct_withdrawals() {
+    let program_id = Pubkey::new_unique();
+    let mut program_test = ProgramTest::new("exchangebooth", program_id, processor!(Processor::process_instruction));
+
+    let admin = Keypair::new();
+    let (mint_a, mint_b, oracle) = (Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique());
+    let admin_b = Pubkey::new_unique();
+    let (queue_only, limited) = (Pubkey::new_unique(), Pubkey::new_unique());
+
+    program_test.add_account(
+        admin.pubkey(),
+        Account { lamports: 10_000_000_000, owner: system_program::id(), ..Account::default() },
+    );
+    program_test.add_account(mint_a, mint_account());
+    program_test.add_account(mint_b, mint_account());
+    program_test.add_account(admin_b, token_account(&mint_b, &admin.pubkey(), 1_000));
+    for booth in [queue_only, limited] {
+        program_test.add_account(
+            booth,
+            Account {
+                lamports: Rent::default().minimum_balance(ExchangeBooth::LEN),
+                data: vec![0; ExchangeBooth::LEN],
+                owner: program_id,
+                ..Account::default()
+            },
+        );
+    }
+
+    let (mut banks_client, payer, blockhash) = program_test.start().await;
+    let setup = |booth: &Pubkey, withdrawal_limit: u64| {
+        let (vault_b, _) = ExchangeBooth::find_vault_address(booth, b"vault_b", &program_id);
+        [
+            instruction::initialize_exchange_booth(
+                &program_id, &admin.pubkey(), booth, &mint_a, &mint_b, &oracle, &spl_token::id(),
+                30, 0, 0, withdrawal_limit, 100,
+            ),
+            instruction::deposit(
+                &program_id, &admin.pubkey(), &admin_b, &vault_b, &mint_b, booth, &spl_token::id(), 500,
+            ),
+        ]
+    };
+    let withdraw = |booth: &Pubkey, amount: u64| {
+        let (vault_b, _) = ExchangeBooth::find_vault_address(booth, b"vault_b", &program_id);
+        instruction::withdraw(
+            &program_id, &admin.pubkey(), &admin_b, &vault_b, &mint_b, booth, &spl_token::id(), &[], amount,
+        )
+    };
+
+    // without a limit every withdrawal has to be queued
+    send(&mut banks_client, &payer, &admin, blockhash, &setup(&queue_only, 0)).await.unwrap();
+    assert_eq!(
+        send(&mut banks_client, &payer, &admin, blockhash, &[withdraw(&queue_only, 1)]).await,
+        Err(limit_exceeded(0)),
+    );
+
+    // a limit lets that much out per window, and no more
+    send(&mut banks_client, &payer, &admin, blockhash, &setup(&limited, 200)).await.unwrap();
+    send(&mut banks_client, &payer, &admin, blockhash, &[withdraw(&limited, 150)]).await.unwrap();
+    assert_eq!(
+        send(&mut banks_client, &payer, &admin, blockhash, &[withdraw(&limited, 51)]).await,
+        Err(limit_exceeded(0)),
+    );
+    send(&mut banks_client, &payer, &admin, blockhash, &[withdraw(&limited, 50)]).await.unwrap();
+}
@@ -32,7 +32,9 @@ from our_utils import (
     send_create_token_account_ix, 
     get_token_account_pubkey,
     get_token_account_balance,
-    get_vault_pda
+    get_vault_pda,
+    get_registry_pda,
+    get_registry_index_pda
 )
 
 class InitParams(NamedTuple):
@@ -44,6 +46,11 @@ class InitParams(NamedTuple):
     vault_a: PublicKey
     vault_b: PublicKey
     oracle: PublicKey
+    spread_bps: int
+    flash_loan_fee_bps: int
+    referral_share_bps: int
+    withdrawal_limit: int
+    withdrawal_window_slots: int
 
 def init(client, params: InitParams) -> None:
     init_ix = get_init_ix(params)
@@ -53,14 +60,21 @@ def init(client, params: InitParams) -> None:
 
 
 def get_init_ix(params: InitParams) -> TransactionInstruction:
-    data = struct.pack("<B", 0)
+    data = struct.pack(
+        "<BQQQQQ",
+        0,
+        params.spread_bps,
+        params.flash_loan_fee_bps,
+        params.referral_share_bps,
+        params.withdrawal_limit,
+        params.withdrawal_window_slots)
 
     return TransactionInstruction(
         keys=[
             AccountMeta(
                 pubkey=params.admin.public_key,
                 is_signer=True,
-                is_writable=False
+                is_writable=True
             ),
             AccountMeta(
                 pubkey=params.exchange_booth_acct,
@@ -107,6 +121,16 @@ def get_init_ix(params: InitParams) -> TransactionInstruction:
                 is_signer=False,
                 is_writable=False,
             ),
+            AccountMeta(
+                pubkey=get_registry_pda(params.program_id, params.mint_a, params.mint_b)[0],
+                is_signer=False,
+                is_writable=True,
+            ),
+            AccountMeta(
+                pubkey=get_registry_index_pda(params.program_id)[0],
+                is_signer=False,
+                is_writable=True,
+            ),
         ],
         program_id=params.program_id,
         data=data,
@@ -146,7 +170,7 @@ if __name__ == "__main__":
     mint_b_key = create_test_mint(admin)
     
     exchange_booth_keypair = Keypair()
-    exchange_booth_space = 193
+    exchange_booth_space = 437
     # airdrop_sol_to_fee_payer(client, exchange_booth_keypair.public_key)
     create_exchange_booth(client, exchange_booth_keypair,
         program_id, admin, exchange_booth_space)
@@ -166,7 +190,10 @@ if __name__ == "__main__":
             mint_b=mint_b_key,
             vault_a=vault_a_pda,
             vault_b=vault_b_pda,
-            oracle=oracle_keypair.public_key
+            oracle=oracle_keypair.public_key,
+            spread_bps=30,
+            flash_loan_fee_bps=9,
+            referral_share_bps=2000
         ))
 
 
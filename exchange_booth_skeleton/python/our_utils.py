@@ -77,3 +77,10 @@ def get_vault_pda(
     ):
     seeds = [vault_bytes, bytes(exchange_booth)]
     return PublicKey.find_program_address(seeds, program_id)
+
+def get_registry_pda(program_id: PublicKey, mint_a: PublicKey, mint_b: PublicKey):
+    low, high = sorted([bytes(mint_a), bytes(mint_b)])
+    return PublicKey.find_program_address([b"registry", low, high], program_id)
+
+def get_registry_index_pda(program_id: PublicKey):
+    return PublicKey.find_program_address([b"registry_index"], program_id)
@@ -90,7 +90,12 @@ if __name__ == "__main__":
             mint_b=mint_b_key,
             vault_a=vault_a_key,
             vault_b=vault_b_key,
-            oracle=oracle_keypair.public_key))
+            oracle=oracle_keypair.public_key,
+            spread_bps=30,
+            flash_loan_fee_bps=9,
+            referral_share_bps=2000,
+            withdrawal_limit=amount_to_withdraw,
+            withdrawal_window_slots=9000))
 
     token_acct_key = mint_tokens_to(
         mint=mint_b_key,
@@ -81,7 +81,7 @@ def get_withdraw_ix(params: WithdrawParams) -> TransactionInstruction:
             AccountMeta(
                 pubkey=params.exchange_booth_acct,
                 is_signer=False,
-                is_writable=False,
+                is_writable=True,
             ),
             AccountMeta(
                 pubkey=TOKEN_PROGRAM_ID,